@@ -6,6 +6,12 @@
 //! The merge strategy is based on the principle that ratchets can only tighten,
 //! never loosen. When two branches both reduce a count, both reductions are
 //! preserved by taking the minimum value.
+//!
+//! The merge is a true three-way merge: the base (common ancestor) version is
+//! consulted so that a `(rule_id, region)` key deliberately removed on one
+//! side (because the rule was disabled or the region deleted) is not silently
+//! resurrected by the other side still carrying the old count. See
+//! [`resolve_three_way`] for the full resolution table.
 
 use crate::config::counts::CountsManager;
 use crate::types::{RegionPath, RuleId};
@@ -26,6 +32,10 @@ const EXIT_ERROR: i32 = 1;
 ///
 /// The merge result is written to the "ours" file path.
 ///
+/// Equivalent to [`run_merge_driver_with_options`] with loosening conflicts
+/// treated as errors (the default, safer behavior). Pass `--allow-loosening`
+/// on the CLI to suppress that check.
+///
 /// # Arguments
 ///
 /// * `base` - Path to the base/ancestor version
@@ -36,9 +46,28 @@ const EXIT_ERROR: i32 = 1;
 ///
 /// Exit code:
 /// - 0: Success (merge completed)
-/// - 1: Error (parse failure or I/O error)
+/// - 1: Error (parse failure, I/O error, or an unresolved loosening conflict)
 pub fn run_merge_driver(base: &str, ours: &str, theirs: &str) -> i32 {
-    match run_merge_driver_inner(base, ours, theirs) {
+    run_merge_driver_with_options(base, ours, theirs, false)
+}
+
+/// Run the merge driver with explicit control over loosening-conflict detection
+///
+/// The pairwise "minimum wins" merge can never itself *report* a problem, but
+/// it also can't distinguish a legitimate tightening from a case where both
+/// branches independently raised a budget above the common ancestor (e.g.
+/// base `10`, ours `14`, theirs `13`: min yields `13`, still a loosening that
+/// both branches somehow introduced). When `allow_loosening` is `false`
+/// (the default), such keys are written to a `ratchet-counts.conflicts.toml`
+/// sidecar next to `ours` and the merge returns [`EXIT_ERROR`] instead of
+/// silently resolving them away.
+pub fn run_merge_driver_with_options(
+    base: &str,
+    ours: &str,
+    theirs: &str,
+    allow_loosening: bool,
+) -> i32 {
+    match run_merge_driver_inner(base, ours, theirs, allow_loosening) {
         Ok(()) => EXIT_SUCCESS,
         Err(e) => {
             eprintln!("Merge driver error: {}", e);
@@ -47,13 +76,66 @@ pub fn run_merge_driver(base: &str, ours: &str, theirs: &str) -> i32 {
     }
 }
 
+/// Run the `ratchet merge` subcommand, folding N sharded count files into one
+///
+/// This backs `ratchet merge --inputs a.toml b.toml c.toml --base base.toml
+/// -o merged.toml`, for CI setups that shard a ratchet run across parallel
+/// jobs and need to combine the per-shard `ratchet-counts.toml` outputs back
+/// into a single file. Unlike [`run_merge_driver`], this is not invoked by
+/// git and writes the result to an explicit output path rather than
+/// overwriting one of the inputs.
+///
+/// # Returns
+///
+/// Exit code: 0 on success, 1 on parse or I/O error.
+pub fn run_merge_many_command(inputs: &[String], base: &str, output: &str) -> i32 {
+    match run_merge_many_command_inner(inputs, base, output) {
+        Ok(()) => EXIT_SUCCESS,
+        Err(e) => {
+            eprintln!("Merge error: {}", e);
+            EXIT_ERROR
+        }
+    }
+}
+
+fn run_merge_many_command_inner(inputs: &[String], base: &str, output: &str) -> Result<(), String> {
+    let base_counts = parse_counts_file(base, "base")?;
+    let mut sides = Vec::with_capacity(inputs.len());
+    for (i, input) in inputs.iter().enumerate() {
+        sides.push(parse_counts_file(input, &format!("input[{}]", i))?);
+    }
+
+    let merged = merge_many(&base_counts, &sides);
+
+    write_counts_file(output, &merged)
+}
+
 /// Internal implementation of merge driver
-fn run_merge_driver_inner(base: &str, ours: &str, theirs: &str) -> Result<(), String> {
+fn run_merge_driver_inner(
+    base: &str,
+    ours: &str,
+    theirs: &str,
+    allow_loosening: bool,
+) -> Result<(), String> {
     // Parse all three versions
     let base_counts = parse_counts_file(base, "base")?;
     let ours_counts = parse_counts_file(ours, "ours")?;
     let theirs_counts = parse_counts_file(theirs, "theirs")?;
 
+    if !allow_loosening {
+        let conflicts = detect_loosening_conflicts(&base_counts, &ours_counts, &theirs_counts);
+        if !conflicts.is_empty() {
+            let sidecar = conflicts_sidecar_path(ours);
+            write_conflicts_file(&sidecar, &conflicts)?;
+            return Err(format!(
+                "{} rule/region(s) were loosened on both branches relative to the base; \
+                 see '{}' for details, or pass --allow-loosening to merge anyway",
+                conflicts.len(),
+                sidecar.display()
+            ));
+        }
+    }
+
     // Perform the merge
     let merged = merge_counts(&base_counts, &ours_counts, &theirs_counts);
 
@@ -63,6 +145,75 @@ fn run_merge_driver_inner(base: &str, ours: &str, theirs: &str) -> Result<(), St
     Ok(())
 }
 
+/// A `(rule_id, region)` key that both `ours` and `theirs` independently
+/// loosened relative to `base`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoosenConflict {
+    pub rule_id: String,
+    pub region: String,
+    pub base: u64,
+    pub ours: u64,
+    pub theirs: u64,
+}
+
+/// Find every key where both sides strictly increased the budget relative to
+/// the common ancestor
+///
+/// `min(ours, theirs)` would otherwise auto-resolve these without ever
+/// reporting that both branches loosened the same ratchet.
+fn detect_loosening_conflicts(
+    base: &CountsManager,
+    ours: &CountsManager,
+    theirs: &CountsManager,
+) -> Vec<LoosenConflict> {
+    let base_map = counts_map(base);
+    let ours_map = counts_map(ours);
+    let theirs_map = counts_map(theirs);
+
+    let mut conflicts = Vec::new();
+    for (key, &b) in &base_map {
+        if let (Some(&o), Some(&t)) = (ours_map.get(key), theirs_map.get(key))
+            && o > b
+            && t > b
+        {
+            conflicts.push(LoosenConflict {
+                rule_id: key.0.clone(),
+                region: key.1.clone(),
+                base: b,
+                ours: o,
+                theirs: t,
+            });
+        }
+    }
+
+    conflicts.sort_by(|a, b| a.rule_id.cmp(&b.rule_id).then_with(|| a.region.cmp(&b.region)));
+    conflicts
+}
+
+/// Path of the sidecar conflicts file, placed next to the `ours` file
+fn conflicts_sidecar_path(ours: &str) -> std::path::PathBuf {
+    Path::new(ours)
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join("ratchet-counts.conflicts.toml")
+}
+
+/// Write detected loosening conflicts to a sidecar TOML file
+fn write_conflicts_file(path: &std::path::Path, conflicts: &[LoosenConflict]) -> Result<(), String> {
+    let mut out = String::new();
+    for conflict in conflicts {
+        out.push_str("[[conflict]]\n");
+        out.push_str(&format!("rule = \"{}\"\n", conflict.rule_id));
+        out.push_str(&format!("region = \"{}\"\n", conflict.region));
+        out.push_str(&format!("base = {}\n", conflict.base));
+        out.push_str(&format!("ours = {}\n", conflict.ours));
+        out.push_str(&format!("theirs = {}\n\n", conflict.theirs));
+    }
+
+    std::fs::write(path, out)
+        .map_err(|e| format!("Failed to write conflicts file '{}': {}", path.display(), e))
+}
+
 /// Parse a counts file, treating missing or empty files as empty CountsManager
 fn parse_counts_file(path: &str, label: &str) -> Result<CountsManager, String> {
     let path_obj = Path::new(path);
@@ -87,62 +238,159 @@ fn write_counts_file(path: &str, counts: &CountsManager) -> Result<(), String> {
         .map_err(|e| format!("Failed to write merged counts to '{}': {}", path, e))
 }
 
-/// Merge three versions of counts using "minimum wins" strategy
-///
-/// For each (rule_id, region) combination:
-/// - If present in both ours and theirs: take minimum
-/// - If present in only one: use that value
-/// - If present in neither: skip (not in merged result)
+/// Merge three versions of counts using "minimum wins" three-way semantics
 ///
-/// The base version is currently not used in the merge logic, but is accepted
-/// for potential future three-way merge strategies.
+/// For each (rule_id, region) combination, the base, ours, and theirs values
+/// are classified relative to the common ancestor and resolved via
+/// [`resolve_three_way`]. A key absent from the base is treated as today:
+/// present in both sides takes the minimum, present in one side uses that
+/// value, present in neither is skipped.
 fn merge_counts(
-    _base: &CountsManager,
+    base: &CountsManager,
     ours: &CountsManager,
     theirs: &CountsManager,
 ) -> CountsManager {
     let mut merged = CountsManager::new();
 
-    // Extract all counts into maps for easy lookup
-    let ours_counts = extract_all_counts(ours);
-    let theirs_counts = extract_all_counts(theirs);
-
-    // Build a map for fast lookup
-    let mut ours_map: HashMap<(String, String), u64> = HashMap::new();
-    for (rule_id, region, count) in &ours_counts {
-        ours_map.insert(
-            (rule_id.as_str().to_string(), region.as_str().to_string()),
-            *count,
-        );
-    }
-
-    let mut theirs_map: HashMap<(String, String), u64> = HashMap::new();
-    for (rule_id, region, count) in &theirs_counts {
-        theirs_map.insert(
-            (rule_id.as_str().to_string(), region.as_str().to_string()),
-            *count,
-        );
-    }
+    let base_map = counts_map(base);
+    let ours_map = counts_map(ours);
+    let theirs_map = counts_map(theirs);
 
-    // Collect all unique keys
+    // Collect all unique keys across all three versions
     let mut all_keys: HashSet<(String, String)> = HashSet::new();
+    all_keys.extend(base_map.keys().cloned());
     all_keys.extend(ours_map.keys().cloned());
     all_keys.extend(theirs_map.keys().cloned());
 
-    // For each key, take the minimum of the two values (or the only value if present in one)
-    for (rule_id_str, region_str) in all_keys {
-        let ours_count = ours_map.get(&(rule_id_str.clone(), region_str.clone()));
-        let theirs_count = theirs_map.get(&(rule_id_str.clone(), region_str.clone()));
+    for key @ (ref rule_id_str, ref region_str) in all_keys {
+        let b = base_map.get(&key).copied();
+        let o = ours_map.get(&key).copied();
+        let t = theirs_map.get(&key).copied();
 
-        let final_count = match (ours_count, theirs_count) {
-            (Some(&o), Some(&t)) => std::cmp::min(o, t),
-            (Some(&o), None) => o,
-            (None, Some(&t)) => t,
-            (None, None) => continue, // Should never happen
+        if let Some(final_count) = resolve_three_way(b, o, t)
+            && let Some(rule_id) = RuleId::new(rule_id_str)
+        {
+            let region = RegionPath::new(region_str.clone());
+            merged.set_count(&rule_id, &region, final_count);
+        }
+    }
+
+    merged
+}
+
+/// How a side's value relates to the common ancestor's value for one key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SideState {
+    /// The side matches the base exactly
+    Unchanged,
+    /// The side removed a key that was present in the base
+    Deleted,
+    /// The side has a different value than the base
+    Changed(u64),
+}
+
+fn classify(side: Option<u64>, base: u64) -> SideState {
+    match side {
+        None => SideState::Deleted,
+        Some(v) if v == base => SideState::Unchanged,
+        Some(v) => SideState::Changed(v),
+    }
+}
+
+/// Resolve the three-way merge for a single `(rule_id, region)` key
+///
+/// `base` is `None` when the key did not exist in the common ancestor, in
+/// which case deletion semantics don't apply and we fall back to the
+/// pre-existing "minimum of whatever is present" behavior. Otherwise each
+/// side is classified as unchanged, deleted, or changed relative to the base
+/// and resolved as follows:
+///
+/// - both unchanged -> base
+/// - one changed, other unchanged -> the changed side
+/// - both changed to the same value -> that value
+/// - both changed to different values -> `min(ours, theirs)` (tighten wins)
+/// - deletion + unchanged -> stays deleted
+/// - deletion + deletion -> stays deleted
+/// - deletion + a tightening change (`new <= base`) -> the tightened value wins
+/// - deletion + a loosening change (`new > base`) -> stays deleted
+///
+/// Returns `None` when the key should be absent from the merged result.
+fn resolve_three_way(base: Option<u64>, ours: Option<u64>, theirs: Option<u64>) -> Option<u64> {
+    let Some(b) = base else {
+        return match (ours, theirs) {
+            (Some(o), Some(t)) => Some(std::cmp::min(o, t)),
+            (Some(o), None) => Some(o),
+            (None, Some(t)) => Some(t),
+            (None, None) => None,
         };
+    };
+
+    match (classify(ours, b), classify(theirs, b)) {
+        (SideState::Unchanged, SideState::Unchanged) => Some(b),
+        (SideState::Changed(v), SideState::Unchanged) => Some(v),
+        (SideState::Unchanged, SideState::Changed(v)) => Some(v),
+        (SideState::Changed(o), SideState::Changed(t)) => Some(std::cmp::min(o, t)),
+        (SideState::Deleted, SideState::Deleted) => None,
+        (SideState::Deleted, SideState::Unchanged) | (SideState::Unchanged, SideState::Deleted) => {
+            None
+        }
+        (SideState::Deleted, SideState::Changed(v)) | (SideState::Changed(v), SideState::Deleted) => {
+            if v <= b {
+                Some(v)
+            } else {
+                None
+            }
+        }
+    }
+}
+
+/// Build a `(rule_id, region) -> count` lookup map from a `CountsManager`
+fn counts_map(counts: &CountsManager) -> HashMap<(String, String), u64> {
+    extract_all_counts(counts)
+        .into_iter()
+        .map(|(rule_id, region, count)| {
+            (
+                (rule_id.as_str().to_string(), region.as_str().to_string()),
+                count,
+            )
+        })
+        .collect()
+}
 
-        if let Some(rule_id) = RuleId::new(&rule_id_str) {
-            let region = RegionPath::new(region_str);
+/// Merge an arbitrary number of sharded count files against a common base
+///
+/// Generalizes [`merge_counts`] the way an n-way conflict generalizes a
+/// pairwise one: for each `(rule_id, region)` key, every present value across
+/// `sides` is classified relative to `base` and resolved with the same
+/// base-aware, tighten-wins rules, extended to more than two terms:
+///
+/// - all sides unchanged (or no sides at all) -> base
+/// - exactly one side changed, the rest unchanged -> that side's value
+/// - two or more sides changed -> the minimum of the changed values
+/// - any side deleted the key and none changed it -> stays deleted
+/// - a deletion alongside a tightening change -> the tightened value wins
+/// - a deletion alongside only loosening changes -> stays deleted
+pub fn merge_many(base: &CountsManager, sides: &[CountsManager]) -> CountsManager {
+    let mut merged = CountsManager::new();
+
+    let base_map = counts_map(base);
+    let side_maps: Vec<HashMap<(String, String), u64>> = sides.iter().map(counts_map).collect();
+
+    let mut all_keys: HashSet<(String, String)> = HashSet::new();
+    all_keys.extend(base_map.keys().cloned());
+    for side_map in &side_maps {
+        all_keys.extend(side_map.keys().cloned());
+    }
+
+    for key @ (ref rule_id_str, ref region_str) in all_keys {
+        let b = base_map.get(&key).copied();
+        let side_values: Vec<Option<u64>> =
+            side_maps.iter().map(|m| m.get(&key).copied()).collect();
+
+        if let Some(final_count) = resolve_n_way(b, &side_values)
+            && let Some(rule_id) = RuleId::new(rule_id_str)
+        {
+            let region = RegionPath::new(region_str.clone());
             merged.set_count(&rule_id, &region, final_count);
         }
     }
@@ -150,38 +398,246 @@ fn merge_counts(
     merged
 }
 
-/// Extract all (rule_id, region, count) tuples from a CountsManager
+/// Resolve the n-way merge for a single `(rule_id, region)` key
 ///
-/// This is a helper function that extracts the internal structure of a
-/// CountsManager for processing during merge.
-fn extract_all_counts(counts: &CountsManager) -> Vec<(RuleId, RegionPath, u64)> {
-    let mut result = Vec::new();
-
-    // We need to access the internal structure of CountsManager
-    // For now, we'll use a workaround: serialize to TOML and parse back
-    let toml_str = counts.to_toml_string();
-
-    // Parse the TOML manually to extract keys
-    if let Ok(parsed) = toml::from_str::<toml::Value>(&toml_str)
-        && let toml::Value::Table(table) = parsed
-    {
-        for (rule_id_str, value) in table {
-            if let Some(rule_id) = RuleId::new(&rule_id_str)
-                && let toml::Value::Table(regions) = value
-            {
-                for (region_str, count_value) in regions {
-                    let region = RegionPath::new(region_str);
-                    if let Some(count) = count_value.as_integer()
-                        && count >= 0
-                    {
-                        result.push((rule_id.clone(), region, count as u64));
-                    }
-                }
+/// See [`merge_many`] for the resolution rules; this is the n-ary
+/// counterpart of [`resolve_three_way`].
+fn resolve_n_way(base: Option<u64>, sides: &[Option<u64>]) -> Option<u64> {
+    let Some(b) = base else {
+        return sides.iter().flatten().copied().min();
+    };
+
+    let states: Vec<SideState> = sides.iter().map(|&s| classify(s, b)).collect();
+
+    let changed: Vec<u64> = states
+        .iter()
+        .filter_map(|s| match s {
+            SideState::Changed(v) => Some(*v),
+            _ => None,
+        })
+        .collect();
+
+    if !changed.is_empty() {
+        let tightest = changed.iter().copied().min().unwrap();
+        // A deletion is only overridden by a change that tightens (or holds)
+        // the budget relative to base; a deletion alongside only loosening
+        // changes stays deleted.
+        let any_deleted = states.iter().any(|s| *s == SideState::Deleted);
+        if any_deleted && tightest > b {
+            return None;
+        }
+        return Some(tightest);
+    }
+
+    if states.iter().any(|s| *s == SideState::Deleted) {
+        return None;
+    }
+
+    // All sides unchanged (or no sides at all)
+    Some(b)
+}
+
+/// How to combine the sides' values for a key that matches a given rule
+///
+/// "Minimum wins" is correct for a budget that only ever shrinks, but wrong
+/// for a count that should monotonically grow (e.g. a migrated-files
+/// counter) or for aggregate totals. `Min` reuses the base-aware
+/// tighten-wins resolution in [`resolve_three_way`]; the other strategies
+/// combine whatever values are present on `ours`/`theirs` directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeStrategy {
+    /// Tighten wins; deletions are honored unless overridden by a tightening
+    /// change. This is the default strategy.
+    Min,
+    /// The largest value wins, for counters that should only grow.
+    Max,
+    /// Values are added together, for aggregate counters.
+    Sum,
+    /// Both sides must already agree; a mismatch is a merge error.
+    RequireEqual,
+}
+
+impl MergeStrategy {
+    fn parse(s: &str) -> Result<Self, String> {
+        match s {
+            "min" => Ok(MergeStrategy::Min),
+            "max" => Ok(MergeStrategy::Max),
+            "sum" => Ok(MergeStrategy::Sum),
+            "require-equal" => Ok(MergeStrategy::RequireEqual),
+            other => Err(format!(
+                "unknown merge strategy '{}' (expected one of: min, max, sum, require-equal)",
+                other
+            )),
+        }
+    }
+}
+
+/// Maps rule-id glob patterns to a [`MergeStrategy`], compiled once from a
+/// TOML `[merge]` config section
+///
+/// ```toml
+/// [merge]
+/// "migrated-files" = "max"
+/// "allowlist-*" = "max"
+/// "coverage-total" = "sum"
+/// "schema-version" = "require-equal"
+/// ```
+///
+/// Patterns are checked in declaration order; the first match wins. A rule
+/// id that matches no pattern uses [`MergeStrategy::Min`].
+pub struct MergeConfig {
+    patterns: Vec<(glob::Pattern, MergeStrategy)>,
+}
+
+impl MergeConfig {
+    /// A config with no overrides: every rule uses [`MergeStrategy::Min`]
+    pub fn empty() -> Self {
+        Self {
+            patterns: Vec::new(),
+        }
+    }
+
+    /// Compile a `[merge]` section from a TOML document
+    ///
+    /// `toml::Value::Table` is a `BTreeMap` unless this workspace happens to
+    /// pull in the `toml`/`indexmap` `preserve_order` feature, so its
+    /// iteration order can't be trusted to match the document's declaration
+    /// order. Since that order is this type's documented first-match-wins
+    /// contract, entries are re-sorted by each key's own byte offset in
+    /// `toml_str` after parsing, rather than used in whatever order the
+    /// parsed table happens to hand them back.
+    pub fn from_toml_str(toml_str: &str) -> Result<Self, String> {
+        let value: toml::Value =
+            toml::from_str(toml_str).map_err(|e| format!("Invalid merge config: {}", e))?;
+
+        let mut patterns = Vec::new();
+        if let Some(toml::Value::Table(table)) = value.get("merge") {
+            let mut entries: Vec<(&String, &toml::Value)> = table.iter().collect();
+            entries.sort_by_key(|(pattern_str, _)| declaration_offset(toml_str, pattern_str));
+
+            for (pattern_str, strategy_value) in entries {
+                let strategy_str = strategy_value.as_str().ok_or_else(|| {
+                    format!("merge strategy for '{}' must be a string", pattern_str)
+                })?;
+                let strategy = MergeStrategy::parse(strategy_str)?;
+                let pattern = glob::Pattern::new(pattern_str)
+                    .map_err(|e| format!("invalid glob pattern '{}': {}", pattern_str, e))?;
+                patterns.push((pattern, strategy));
             }
         }
+
+        Ok(Self { patterns })
+    }
+
+    /// The strategy to use for a given rule id, defaulting to `min`
+    pub fn strategy_for(&self, rule_id: &str) -> MergeStrategy {
+        self.patterns
+            .iter()
+            .find(|(pattern, _)| pattern.matches(rule_id))
+            .map(|(_, strategy)| *strategy)
+            .unwrap_or(MergeStrategy::Min)
+    }
+}
+
+/// The byte offset of `key`'s first occurrence as a quoted TOML key in `source`
+///
+/// Used only to recover declaration order for [`MergeConfig::from_toml_str`]
+/// after parsing through `toml::Value`, which does not preserve it. Falls
+/// back to `usize::MAX` (sorting unmatched keys last) if the key literal
+/// can't be found, which should only happen for a key TOML accepted in some
+/// unquoted or escaped form this substring search doesn't expect.
+fn declaration_offset(source: &str, key: &str) -> usize {
+    source
+        .find(&format!("\"{}\"", key))
+        .or_else(|| source.find(key))
+        .unwrap_or(usize::MAX)
+}
+
+/// Combine the values present on the non-base sides under a given strategy
+fn combine_values(strategy: MergeStrategy, values: &[u64]) -> Result<Option<u64>, String> {
+    if values.is_empty() {
+        return Ok(None);
     }
 
-    result
+    match strategy {
+        MergeStrategy::Min => Ok(values.iter().copied().min()),
+        MergeStrategy::Max => Ok(values.iter().copied().max()),
+        MergeStrategy::Sum => Ok(Some(values.iter().copied().sum())),
+        MergeStrategy::RequireEqual => {
+            let first = values[0];
+            if values.iter().all(|&v| v == first) {
+                Ok(Some(first))
+            } else {
+                Err(format!(
+                    "require-equal merge strategy found conflicting values {:?}",
+                    values
+                ))
+            }
+        }
+    }
+}
+
+/// Merge three versions of counts, dispatching per-rule strategies from `config`
+///
+/// For rules using [`MergeStrategy::Min`] (the default), this matches
+/// [`merge_counts`]'s base-aware, tighten-wins semantics. For any other
+/// strategy the base's deletion tracking doesn't apply; the sides' present
+/// values are combined directly via [`combine_values`].
+pub fn merge_counts_with_strategies(
+    base: &CountsManager,
+    ours: &CountsManager,
+    theirs: &CountsManager,
+    config: &MergeConfig,
+) -> Result<CountsManager, String> {
+    let mut merged = CountsManager::new();
+
+    let base_map = counts_map(base);
+    let ours_map = counts_map(ours);
+    let theirs_map = counts_map(theirs);
+
+    let mut all_keys: HashSet<(String, String)> = HashSet::new();
+    all_keys.extend(base_map.keys().cloned());
+    all_keys.extend(ours_map.keys().cloned());
+    all_keys.extend(theirs_map.keys().cloned());
+
+    for key @ (ref rule_id_str, ref region_str) in all_keys {
+        let strategy = config.strategy_for(rule_id_str);
+
+        let final_count = match strategy {
+            MergeStrategy::Min => resolve_three_way(
+                base_map.get(&key).copied(),
+                ours_map.get(&key).copied(),
+                theirs_map.get(&key).copied(),
+            ),
+            _ => {
+                let values: Vec<u64> = [ours_map.get(&key).copied(), theirs_map.get(&key).copied()]
+                    .into_iter()
+                    .flatten()
+                    .collect();
+                combine_values(strategy, &values)
+                    .map_err(|e| format!("rule '{}' region '{}': {}", rule_id_str, region_str, e))?
+            }
+        };
+
+        if let Some(final_count) = final_count
+            && let Some(rule_id) = RuleId::new(rule_id_str)
+        {
+            let region = RegionPath::new(region_str.clone());
+            merged.set_count(&rule_id, &region, final_count);
+        }
+    }
+
+    Ok(merged)
+}
+
+/// Extract all (rule_id, region, count) tuples from a CountsManager
+///
+/// Delegates to [`CountsManager::entries`], which walks the manager's
+/// internal map directly. Previously this went through a TOML
+/// serialize/re-parse round trip; that's gone now, along with the silent
+/// drop of any rule id `RuleId::new` happened to reject along the way.
+fn extract_all_counts(counts: &CountsManager) -> Vec<(RuleId, RegionPath, u64)> {
+    counts.entries().collect()
 }
 
 #[cfg(test)]
@@ -539,4 +995,397 @@ mod tests {
         assert_eq!(merged.get_budget(&rule2, Path::new(".")), 10);
         assert_eq!(merged.get_budget(&rule3, Path::new("src/file.rs")), 5);
     }
+
+    #[test]
+    fn test_merge_counts_deletion_vs_unchanged_stays_deleted() {
+        let mut base = CountsManager::new();
+        let mut theirs = CountsManager::new();
+        let ours = CountsManager::new();
+
+        let rule_id = RuleId::new("no-unwrap").unwrap();
+        let region = RegionPath::new(".");
+
+        // Present in base, unchanged in theirs, removed by ours
+        base.set_count(&rule_id, &region, 20);
+        theirs.set_count(&rule_id, &region, 20);
+
+        let merged = merge_counts(&base, &ours, &theirs);
+
+        // Deletion on one side + unchanged on the other: key stays deleted
+        assert_eq!(merged.get_budget(&rule_id, Path::new(".")), 0);
+    }
+
+    #[test]
+    fn test_merge_counts_deletion_vs_tighten_keeps_tightened_value() {
+        let mut base = CountsManager::new();
+        let mut theirs = CountsManager::new();
+        let ours = CountsManager::new();
+
+        let rule_id = RuleId::new("no-unwrap").unwrap();
+        let region = RegionPath::new(".");
+
+        base.set_count(&rule_id, &region, 20);
+        // Ours deleted the key (region/rule no longer tracked)
+        // Theirs tightened the budget
+        theirs.set_count(&rule_id, &region, 12);
+
+        let merged = merge_counts(&base, &ours, &theirs);
+
+        // Tightening beats deletion
+        assert_eq!(merged.get_budget(&rule_id, Path::new(".")), 12);
+    }
+
+    #[test]
+    fn test_merge_counts_deletion_vs_loosen_stays_deleted() {
+        let mut base = CountsManager::new();
+        let mut theirs = CountsManager::new();
+        let ours = CountsManager::new();
+
+        let rule_id = RuleId::new("no-unwrap").unwrap();
+        let region = RegionPath::new(".");
+
+        base.set_count(&rule_id, &region, 20);
+        // Ours deleted the key; theirs loosened it instead of tightening
+        theirs.set_count(&rule_id, &region, 25);
+
+        let merged = merge_counts(&base, &ours, &theirs);
+
+        // A loosening change does not resurrect a deliberate deletion
+        assert_eq!(merged.get_budget(&rule_id, Path::new(".")), 0);
+    }
+
+    #[test]
+    fn test_resolve_three_way_no_base() {
+        assert_eq!(resolve_three_way(None, Some(10), Some(15)), Some(10));
+        assert_eq!(resolve_three_way(None, Some(10), None), Some(10));
+        assert_eq!(resolve_three_way(None, None, Some(15)), Some(15));
+        assert_eq!(resolve_three_way(None, None, None), None);
+    }
+
+    #[test]
+    fn test_resolve_three_way_both_unchanged() {
+        assert_eq!(resolve_three_way(Some(20), Some(20), Some(20)), Some(20));
+    }
+
+    #[test]
+    fn test_resolve_three_way_both_deleted() {
+        assert_eq!(resolve_three_way(Some(20), None, None), None);
+    }
+
+    #[test]
+    fn test_merge_many_takes_minimum_across_shards() {
+        let mut base = CountsManager::new();
+        let mut a = CountsManager::new();
+        let mut b = CountsManager::new();
+        let mut c = CountsManager::new();
+
+        let rule_id = RuleId::new("no-unwrap").unwrap();
+        let region = RegionPath::new(".");
+
+        base.set_count(&rule_id, &region, 20);
+        a.set_count(&rule_id, &region, 15);
+        b.set_count(&rule_id, &region, 18);
+        c.set_count(&rule_id, &region, 16);
+
+        let merged = merge_many(&base, &[a, b, c]);
+
+        assert_eq!(merged.get_budget(&rule_id, Path::new(".")), 15);
+    }
+
+    #[test]
+    fn test_merge_many_deletion_vs_unchanged_stays_deleted() {
+        let mut base = CountsManager::new();
+        let a = CountsManager::new();
+        let mut b = CountsManager::new();
+
+        let rule_id = RuleId::new("no-unwrap").unwrap();
+        let region = RegionPath::new(".");
+
+        base.set_count(&rule_id, &region, 20);
+        b.set_count(&rule_id, &region, 20);
+
+        let merged = merge_many(&base, &[a, b]);
+
+        assert_eq!(merged.get_budget(&rule_id, Path::new(".")), 0);
+    }
+
+    #[test]
+    fn test_merge_many_deletion_vs_tighten_keeps_tightened_value() {
+        let mut base = CountsManager::new();
+        let a = CountsManager::new();
+        let mut b = CountsManager::new();
+
+        let rule_id = RuleId::new("no-unwrap").unwrap();
+        let region = RegionPath::new(".");
+
+        base.set_count(&rule_id, &region, 20);
+        b.set_count(&rule_id, &region, 10);
+
+        let merged = merge_many(&base, &[a, b]);
+
+        assert_eq!(merged.get_budget(&rule_id, Path::new(".")), 10);
+    }
+
+    #[test]
+    fn test_merge_many_new_key_across_shards() {
+        let base = CountsManager::new();
+        let mut a = CountsManager::new();
+        let mut b = CountsManager::new();
+
+        let rule_id = RuleId::new("no-todo").unwrap();
+        let region = RegionPath::new("src");
+
+        a.set_count(&rule_id, &region, 12);
+        b.set_count(&rule_id, &region, 9);
+
+        let merged = merge_many(&base, &[a, b]);
+
+        assert_eq!(merged.get_budget(&rule_id, Path::new("src/file.rs")), 9);
+    }
+
+    #[test]
+    fn test_run_merge_many_command_writes_output() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let base = create_test_file(&temp_dir, "base.toml", "");
+        let a = create_test_file(
+            &temp_dir,
+            "a.toml",
+            r#"
+[no-unwrap]
+"." = 15
+"#,
+        );
+        let b = create_test_file(
+            &temp_dir,
+            "b.toml",
+            r#"
+[no-unwrap]
+"." = 18
+"#,
+        );
+        let output = temp_dir.path().join("merged.toml");
+
+        let result =
+            run_merge_many_command(&[a, b], &base, output.to_str().unwrap());
+        assert_eq!(result, EXIT_SUCCESS);
+
+        let merged_content = fs::read_to_string(&output).unwrap();
+        let merged_counts = CountsManager::parse(&merged_content).unwrap();
+        let rule_id = RuleId::new("no-unwrap").unwrap();
+        assert_eq!(merged_counts.get_budget(&rule_id, Path::new(".")), 15);
+    }
+
+    #[test]
+    fn test_detect_loosening_conflicts_both_sides_loosened() {
+        let mut base = CountsManager::new();
+        let mut ours = CountsManager::new();
+        let mut theirs = CountsManager::new();
+
+        let rule_id = RuleId::new("no-unwrap").unwrap();
+        let region = RegionPath::new(".");
+
+        base.set_count(&rule_id, &region, 10);
+        ours.set_count(&rule_id, &region, 14);
+        theirs.set_count(&rule_id, &region, 13);
+
+        let conflicts = detect_loosening_conflicts(&base, &ours, &theirs);
+
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].rule_id, "no-unwrap");
+        assert_eq!(conflicts[0].region, ".");
+        assert_eq!(conflicts[0].base, 10);
+        assert_eq!(conflicts[0].ours, 14);
+        assert_eq!(conflicts[0].theirs, 13);
+    }
+
+    #[test]
+    fn test_detect_loosening_conflicts_one_side_tightened_is_not_a_conflict() {
+        let mut base = CountsManager::new();
+        let mut ours = CountsManager::new();
+        let mut theirs = CountsManager::new();
+
+        let rule_id = RuleId::new("no-unwrap").unwrap();
+        let region = RegionPath::new(".");
+
+        base.set_count(&rule_id, &region, 10);
+        ours.set_count(&rule_id, &region, 14);
+        theirs.set_count(&rule_id, &region, 5);
+
+        let conflicts = detect_loosening_conflicts(&base, &ours, &theirs);
+
+        assert!(conflicts.is_empty());
+    }
+
+    #[test]
+    fn test_run_merge_driver_rejects_double_loosening_by_default() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let base = create_test_file(&temp_dir, "base.toml", "[no-unwrap]\n\".\" = 10\n");
+        let ours = create_test_file(&temp_dir, "ours.toml", "[no-unwrap]\n\".\" = 14\n");
+        let theirs = create_test_file(&temp_dir, "theirs.toml", "[no-unwrap]\n\".\" = 13\n");
+
+        let result = run_merge_driver(&base, &ours, &theirs);
+        assert_eq!(result, EXIT_ERROR);
+
+        let sidecar = temp_dir.path().join("ratchet-counts.conflicts.toml");
+        assert!(sidecar.exists());
+        let contents = fs::read_to_string(&sidecar).unwrap();
+        assert!(contents.contains("no-unwrap"));
+        assert!(contents.contains("base = 10"));
+        assert!(contents.contains("ours = 14"));
+        assert!(contents.contains("theirs = 13"));
+    }
+
+    #[test]
+    fn test_run_merge_driver_allow_loosening_suppresses_conflict() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let base = create_test_file(&temp_dir, "base.toml", "[no-unwrap]\n\".\" = 10\n");
+        let ours = create_test_file(&temp_dir, "ours.toml", "[no-unwrap]\n\".\" = 14\n");
+        let theirs = create_test_file(&temp_dir, "theirs.toml", "[no-unwrap]\n\".\" = 13\n");
+
+        let result = run_merge_driver_with_options(&base, &ours, &theirs, true);
+        assert_eq!(result, EXIT_SUCCESS);
+
+        let merged_content = fs::read_to_string(&ours).unwrap();
+        let merged_counts = CountsManager::parse(&merged_content).unwrap();
+        let rule_id = RuleId::new("no-unwrap").unwrap();
+        assert_eq!(merged_counts.get_budget(&rule_id, Path::new(".")), 13);
+    }
+
+    #[test]
+    fn test_merge_config_empty_defaults_to_min() {
+        let config = MergeConfig::empty();
+        assert_eq!(config.strategy_for("anything"), MergeStrategy::Min);
+    }
+
+    #[test]
+    fn test_merge_config_parses_strategies_by_glob() {
+        let config = MergeConfig::from_toml_str(
+            r#"
+[merge]
+"migrated-files" = "max"
+"allowlist-*" = "max"
+"coverage-total" = "sum"
+"schema-version" = "require-equal"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.strategy_for("migrated-files"), MergeStrategy::Max);
+        assert_eq!(config.strategy_for("allowlist-python"), MergeStrategy::Max);
+        assert_eq!(config.strategy_for("coverage-total"), MergeStrategy::Sum);
+        assert_eq!(
+            config.strategy_for("schema-version"),
+            MergeStrategy::RequireEqual
+        );
+        // No matching pattern falls back to min
+        assert_eq!(config.strategy_for("no-unwrap"), MergeStrategy::Min);
+    }
+
+    #[test]
+    fn test_merge_config_declaration_order_wins_over_alphabetical_order() {
+        // "zzz-rule" is declared before the catch-all "*", so it should win
+        // for a rule id that matches both — even though "*" sorts before
+        // "zzz-rule" alphabetically (and so would win if `from_toml_str`
+        // relied on `toml::Value::Table`'s BTreeMap iteration order instead
+        // of the document's actual declaration order).
+        let config = MergeConfig::from_toml_str(
+            r#"
+[merge]
+"zzz-rule" = "max"
+"*" = "min"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.strategy_for("zzz-rule"), MergeStrategy::Max);
+    }
+
+    #[test]
+    fn test_merge_config_rejects_unknown_strategy() {
+        let result = MergeConfig::from_toml_str(
+            r#"
+[merge]
+"no-unwrap" = "bogus"
+"#,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_merge_counts_with_strategies_max_wins_for_growing_counter() {
+        let mut base = CountsManager::new();
+        let mut ours = CountsManager::new();
+        let mut theirs = CountsManager::new();
+
+        let rule_id = RuleId::new("migrated-files").unwrap();
+        let region = RegionPath::new(".");
+
+        base.set_count(&rule_id, &region, 10);
+        ours.set_count(&rule_id, &region, 15);
+        theirs.set_count(&rule_id, &region, 12);
+
+        let config = MergeConfig::from_toml_str("[merge]\n\"migrated-files\" = \"max\"\n").unwrap();
+        let merged = merge_counts_with_strategies(&base, &ours, &theirs, &config).unwrap();
+
+        assert_eq!(merged.get_budget(&rule_id, Path::new(".")), 15);
+    }
+
+    #[test]
+    fn test_merge_counts_with_strategies_sum_adds_sides() {
+        let base = CountsManager::new();
+        let mut ours = CountsManager::new();
+        let mut theirs = CountsManager::new();
+
+        let rule_id = RuleId::new("coverage-total").unwrap();
+        let region = RegionPath::new(".");
+
+        ours.set_count(&rule_id, &region, 4);
+        theirs.set_count(&rule_id, &region, 6);
+
+        let config = MergeConfig::from_toml_str("[merge]\n\"coverage-total\" = \"sum\"\n").unwrap();
+        let merged = merge_counts_with_strategies(&base, &ours, &theirs, &config).unwrap();
+
+        assert_eq!(merged.get_budget(&rule_id, Path::new(".")), 10);
+    }
+
+    #[test]
+    fn test_merge_counts_with_strategies_require_equal_errors_on_mismatch() {
+        let base = CountsManager::new();
+        let mut ours = CountsManager::new();
+        let mut theirs = CountsManager::new();
+
+        let rule_id = RuleId::new("schema-version").unwrap();
+        let region = RegionPath::new(".");
+
+        ours.set_count(&rule_id, &region, 3);
+        theirs.set_count(&rule_id, &region, 4);
+
+        let config =
+            MergeConfig::from_toml_str("[merge]\n\"schema-version\" = \"require-equal\"\n").unwrap();
+        let result = merge_counts_with_strategies(&base, &ours, &theirs, &config);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_merge_counts_with_strategies_default_min_matches_merge_counts() {
+        let mut base = CountsManager::new();
+        let mut ours = CountsManager::new();
+        let mut theirs = CountsManager::new();
+
+        let rule_id = RuleId::new("no-unwrap").unwrap();
+        let region = RegionPath::new(".");
+
+        base.set_count(&rule_id, &region, 20);
+        ours.set_count(&rule_id, &region, 15);
+        theirs.set_count(&rule_id, &region, 18);
+
+        let config = MergeConfig::empty();
+        let merged = merge_counts_with_strategies(&base, &ours, &theirs, &config).unwrap();
+
+        assert_eq!(merged.get_budget(&rule_id, Path::new(".")), 15);
+    }
 }
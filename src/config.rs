@@ -1,7 +1,13 @@
 //! Configuration file parsing and validation
 
+pub mod autofix;
+pub mod comment_tags;
 pub mod ratchet_toml;
+pub mod todo_format;
 
+pub use autofix::{apply_edits, fix_for, resolve_edits, Edit, EditConflict};
+pub use comment_tags::{CommentTagsConfig, DEFAULT_COMMENT_TAGS};
 pub use ratchet_toml::{
     ColorOption, Config, OutputConfig, OutputFormat, RuleSettings, RulesConfig,
 };
+pub use todo_format::{check_format, FormatFinding, FormatIssue, TodoFormatConfig};
@@ -0,0 +1,169 @@
+//! Byte-range edits for mechanically-fixable [`super::todo_format`] findings
+//!
+//! An [`Edit`] is a single, independent replacement within a comment's text.
+//! [`resolve_edits`] sorts a batch and rejects overlaps before anything is
+//! applied, and [`apply_edits`] applies a resolved batch back-to-front so
+//! earlier edits don't invalidate the byte offsets of later ones — the same
+//! shape a caller driving a `--fix` flag would need before rewriting a file.
+
+use super::todo_format::{FormatFinding, FormatIssue};
+use std::ops::Range;
+
+/// A single replacement of `span` (byte offsets into the text the finding came from) with `replacement`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edit {
+    /// The byte range being replaced
+    pub span: Range<usize>,
+    /// The text to put in `span`'s place
+    pub replacement: String,
+}
+
+/// Two edits whose spans overlap, so neither can be applied without risking corrupting the other
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditConflict {
+    /// The earlier-starting edit of the conflicting pair
+    pub first: Edit,
+    /// The edit whose span starts before `first`'s ends
+    pub second: Edit,
+}
+
+/// Returns the mechanical fix for `finding`, if one can be derived without guessing content
+///
+/// [`FormatIssue::MissingAuthor`] has no fix: the rule has no way to know who
+/// the author should be, so it's reported but never auto-applied. The other
+/// three issues are purely mechanical rewrites of `tag`'s surrounding
+/// punctuation and casing.
+pub fn fix_for(finding: &FormatFinding, tag: &str) -> Option<Edit> {
+    let replacement = match &finding.issue {
+        FormatIssue::NonCanonicalCase { .. } => tag.to_string(),
+        FormatIssue::MissingColon => ":".to_string(),
+        FormatIssue::MissingSpaceAfterColon => ": ".to_string(),
+        FormatIssue::MissingAuthor => return None,
+    };
+    Some(Edit {
+        span: finding.span.clone(),
+        replacement,
+    })
+}
+
+/// Sorts `edits` by span start and rejects the batch if any two spans overlap
+///
+/// Adjacent, non-overlapping spans (where one ends exactly where the next
+/// begins) are allowed, since inserting at the same cursor position twice
+/// (e.g. a missing colon immediately followed by a missing space) still
+/// produces an unambiguous result once applied in span order.
+pub fn resolve_edits(mut edits: Vec<Edit>) -> Result<Vec<Edit>, EditConflict> {
+    edits.sort_by_key(|edit| edit.span.start);
+    for pair in edits.windows(2) {
+        let (first, second) = (&pair[0], &pair[1]);
+        if second.span.start < first.span.end {
+            return Err(EditConflict {
+                first: first.clone(),
+                second: second.clone(),
+            });
+        }
+    }
+    Ok(edits)
+}
+
+/// Applies a batch of non-overlapping `edits` to `text`, back-to-front so earlier spans stay valid
+///
+/// `edits` must already be sorted and overlap-checked, e.g. via [`resolve_edits`].
+pub fn apply_edits(text: &str, edits: &[Edit]) -> String {
+    let mut result = text.to_string();
+    for edit in edits.iter().rev() {
+        result.replace_range(edit.span.clone(), &edit.replacement);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::todo_format::{check_format, TodoFormatConfig};
+
+    fn all_checks() -> TodoFormatConfig {
+        TodoFormatConfig {
+            require_colon: true,
+            require_author: true,
+            require_space_after_colon: true,
+            require_canonical_case: true,
+        }
+    }
+
+    #[test]
+    fn test_fix_for_missing_author_is_none() {
+        let findings = check_format("TODO: fix this", "TODO", &all_checks());
+        assert_eq!(fix_for(&findings[0], "TODO"), None);
+    }
+
+    #[test]
+    fn test_fix_for_non_canonical_case_replaces_marker() {
+        let findings = check_format("todo(alice): fix", "TODO", &all_checks());
+        let edit = fix_for(&findings[0], "TODO").unwrap();
+        assert_eq!(apply_edits("todo(alice): fix", &[edit]), "TODO(alice): fix");
+    }
+
+    #[test]
+    fn test_resolve_edits_sorts_by_span_start() {
+        let edits = vec![
+            Edit {
+                span: 5..5,
+                replacement: "b".to_string(),
+            },
+            Edit {
+                span: 0..0,
+                replacement: "a".to_string(),
+            },
+        ];
+        let resolved = resolve_edits(edits).unwrap();
+        assert_eq!(resolved[0].span, 0..0);
+        assert_eq!(resolved[1].span, 5..5);
+    }
+
+    #[test]
+    fn test_resolve_edits_rejects_overlap() {
+        let edits = vec![
+            Edit {
+                span: 0..4,
+                replacement: "TODO".to_string(),
+            },
+            Edit {
+                span: 2..6,
+                replacement: "x".to_string(),
+            },
+        ];
+        assert!(resolve_edits(edits).is_err());
+    }
+
+    #[test]
+    fn test_resolve_edits_allows_adjacent_spans() {
+        let edits = vec![
+            Edit {
+                span: 4..4,
+                replacement: ":".to_string(),
+            },
+            Edit {
+                span: 4..4,
+                replacement: " ".to_string(),
+            },
+        ];
+        assert!(resolve_edits(edits).is_ok());
+    }
+
+    #[test]
+    fn test_apply_edits_applies_back_to_front_without_shifting_earlier_spans() {
+        let edits = resolve_edits(vec![
+            Edit {
+                span: 0..4,
+                replacement: "TODO".to_string(),
+            },
+            Edit {
+                span: 11..11,
+                replacement: ":".to_string(),
+            },
+        ])
+        .unwrap();
+        assert_eq!(apply_edits("todo(alice) fix", &edits), "TODO(alice): fix");
+    }
+}
@@ -0,0 +1,217 @@
+//! Configuration for which comment markers the comment-marker AST rules ratchet
+//!
+//! `rust-no-todo-comments` and `rust-no-fixme-comments` used to hardcode
+//! their own marker; [`CommentTagsConfig`] lets a `ratchet.toml` declare the
+//! exact marker vocabulary instead (e.g. adding `HACK`/`XXX`), so a team can
+//! extend it without a new rule shipping per marker. [`MarkerTactic`] further
+//! lets a marker that already carries an issue reference (`TODO(#123):`) be
+//! exempted from the ones still worth flagging.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// The marker vocabulary used when a `ratchet.toml` doesn't set `comment-tags`
+///
+/// Matches the two rules this config option replaces: `rust-no-todo-comments`
+/// and `rust-no-fixme-comments`.
+pub const DEFAULT_COMMENT_TAGS: &[&str] = &["TODO", "FIXME"];
+
+/// The pattern used when a `ratchet.toml` doesn't set `reference-pattern`
+///
+/// Matches `(#123)`, `(alice)`, or a bare `#123` immediately after the marker
+/// word (optionally preceded by whitespace), mirroring the attribution styles
+/// the comment fixtures already use.
+pub const DEFAULT_REFERENCE_PATTERN: &str = r"^\s*(\(\s*(#\d+|[A-Za-z][\w-]*)\s*\)|#\d+)";
+
+/// How a marker that's missing an issue reference should be treated, relative
+/// to one that carries one (e.g. `TODO:` vs. `TODO(#123):`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum MarkerTactic {
+    /// Flag every configured marker, whether or not it carries a reference
+    Always,
+    /// Flag only markers that don't carry a reference matching `reference-pattern`
+    Unnumbered,
+    /// Don't flag markers at all
+    Never,
+}
+
+impl Default for MarkerTactic {
+    fn default() -> Self {
+        MarkerTactic::Always
+    }
+}
+
+/// Declares which comment markers the comment-marker AST rules should flag
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommentTagsConfig {
+    /// The markers to flag, e.g. `["TODO", "FIXME", "HACK", "XXX"]`
+    ///
+    /// Matching is case-insensitive and anchored to the start of the
+    /// comment body, mirroring how the fixed `TODO`/`FIXME` rules matched
+    /// a leading marker rather than the tag anywhere in the comment.
+    #[serde(rename = "comment-tags", default = "default_comment_tags")]
+    pub comment_tags: Vec<String>,
+
+    /// Whether a marker carrying an issue reference is still flagged; see [`MarkerTactic`]
+    #[serde(default)]
+    pub tactic: MarkerTactic,
+
+    /// The regex an issue reference must match, consulted only when `tactic` is `Unnumbered`
+    #[serde(rename = "reference-pattern", default = "default_reference_pattern")]
+    pub reference_pattern: String,
+}
+
+fn default_comment_tags() -> Vec<String> {
+    DEFAULT_COMMENT_TAGS
+        .iter()
+        .map(|tag| tag.to_string())
+        .collect()
+}
+
+fn default_reference_pattern() -> String {
+    DEFAULT_REFERENCE_PATTERN.to_string()
+}
+
+impl Default for CommentTagsConfig {
+    fn default() -> Self {
+        CommentTagsConfig {
+            comment_tags: default_comment_tags(),
+            tactic: MarkerTactic::default(),
+            reference_pattern: default_reference_pattern(),
+        }
+    }
+}
+
+impl CommentTagsConfig {
+    /// Returns the configured tag that opens `comment_text`, if any
+    ///
+    /// `comment_text` is expected to already have its `//`/`///`/`/*`/`/**`
+    /// delimiter stripped — the comment-marker AST rules are responsible for
+    /// that, since only they know which comment-node kind they matched.
+    pub fn matching_tag(&self, comment_text: &str) -> Option<&str> {
+        let trimmed = comment_text.trim_start();
+        self.comment_tags
+            .iter()
+            .find(|tag| {
+                trimmed
+                    .get(..tag.len())
+                    .is_some_and(|prefix| prefix.eq_ignore_ascii_case(tag))
+            })
+            .map(String::as_str)
+    }
+
+    /// Compiles `reference_pattern`, for a caller to reuse across many [`flagged_tag`](Self::flagged_tag) calls
+    pub fn compile_reference_pattern(&self) -> Result<Regex, regex::Error> {
+        Regex::new(&self.reference_pattern)
+    }
+
+    /// Returns the configured tag that should be flagged in `comment_text`, honoring `tactic`
+    ///
+    /// Returns `None` when `comment_text` doesn't open with a configured tag,
+    /// when `tactic` is `Never`, or when `tactic` is `Unnumbered` and the text
+    /// immediately following the tag matches `reference_regex` (compiled from
+    /// `reference_pattern` via [`compile_reference_pattern`](Self::compile_reference_pattern)).
+    pub fn flagged_tag(&self, comment_text: &str, reference_regex: &Regex) -> Option<&str> {
+        let tag = self.matching_tag(comment_text)?;
+        match self.tactic {
+            MarkerTactic::Never => None,
+            MarkerTactic::Always => Some(tag),
+            MarkerTactic::Unnumbered => {
+                let trimmed = comment_text.trim_start();
+                let rest = &trimmed[tag.len()..];
+                if reference_regex.is_match(rest) {
+                    None
+                } else {
+                    Some(tag)
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_is_todo_and_fixme() {
+        let config = CommentTagsConfig::default();
+        assert_eq!(config.comment_tags, vec!["TODO", "FIXME"]);
+    }
+
+    #[test]
+    fn test_matching_tag_is_case_insensitive() {
+        let config = CommentTagsConfig::default();
+        assert_eq!(config.matching_tag("todo: lowercase"), Some("TODO"));
+        assert_eq!(config.matching_tag("FIXME: shouting"), Some("FIXME"));
+    }
+
+    #[test]
+    fn test_matching_tag_requires_leading_position() {
+        let config = CommentTagsConfig::default();
+        assert_eq!(config.matching_tag("This is a TODO comment"), None);
+    }
+
+    #[test]
+    fn test_matching_tag_skips_leading_whitespace() {
+        let config = CommentTagsConfig::default();
+        assert_eq!(config.matching_tag("   TODO: indented"), Some("TODO"));
+    }
+
+    #[test]
+    fn test_matching_tag_returns_none_for_unconfigured_marker() {
+        let config = CommentTagsConfig::default();
+        assert_eq!(config.matching_tag("HACK: not configured by default"), None);
+    }
+
+    #[test]
+    fn test_matching_tag_does_not_panic_on_multi_byte_leading_text() {
+        let config = CommentTagsConfig::default();
+        assert_eq!(
+            config.matching_tag("世界and then some more text that runs past TODO's length"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_custom_tags_extend_the_vocabulary() {
+        let config = CommentTagsConfig {
+            comment_tags: vec!["TODO".to_string(), "HACK".to_string(), "XXX".to_string()],
+            ..CommentTagsConfig::default()
+        };
+        assert_eq!(config.matching_tag("HACK: now configured"), Some("HACK"));
+        assert_eq!(config.matching_tag("FIXME: no longer configured"), None);
+    }
+
+    #[test]
+    fn test_always_tactic_flags_referenced_and_bare_markers_alike() {
+        let config = CommentTagsConfig::default();
+        let re = config.compile_reference_pattern().unwrap();
+        assert_eq!(config.flagged_tag("TODO: bare", &re), Some("TODO"));
+        assert_eq!(config.flagged_tag("TODO(#123): tracked", &re), Some("TODO"));
+    }
+
+    #[test]
+    fn test_never_tactic_flags_nothing() {
+        let config = CommentTagsConfig {
+            tactic: MarkerTactic::Never,
+            ..CommentTagsConfig::default()
+        };
+        let re = config.compile_reference_pattern().unwrap();
+        assert_eq!(config.flagged_tag("TODO: bare", &re), None);
+    }
+
+    #[test]
+    fn test_unnumbered_tactic_suppresses_only_referenced_markers() {
+        let config = CommentTagsConfig {
+            tactic: MarkerTactic::Unnumbered,
+            ..CommentTagsConfig::default()
+        };
+        let re = config.compile_reference_pattern().unwrap();
+        assert_eq!(config.flagged_tag("TODO: bare", &re), Some("TODO"));
+        assert_eq!(config.flagged_tag("TODO(#123): tracked", &re), None);
+        assert_eq!(config.flagged_tag("TODO(alice): tracked", &re), None);
+        assert_eq!(config.flagged_tag("TODO #456 tracked", &re), None);
+    }
+}
@@ -0,0 +1,200 @@
+//! Structured-format checks for TODO/FIXME markers, layered on top of [`super::comment_tags`]
+//!
+//! [`super::comment_tags::CommentTagsConfig`] decides *whether* a marker is
+//! flagged at all; [`TodoFormatConfig`] instead looks at a marker already
+//! known to be flagged and checks its *shape* — `TODO(owner): message`, with
+//! a colon, a space, and canonical casing — independently of one another, so
+//! a team can enable just the checks it cares about.
+
+use serde::{Deserialize, Serialize};
+
+/// Which structured-format checks to run against a flagged marker
+///
+/// Every check defaults to disabled: turning one on is an explicit tightening
+/// of style, not a behavior a team inherits for free by upgrading.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TodoFormatConfig {
+    /// Require a colon after the marker (and author, if present): `TODO:` not `TODO`
+    #[serde(rename = "require-colon", default)]
+    pub require_colon: bool,
+    /// Require a parenthesized author after the marker: `TODO(owner):` not `TODO:`
+    #[serde(rename = "require-author", default)]
+    pub require_author: bool,
+    /// Require a space after the colon: `TODO: fix` not `TODO:fix`
+    #[serde(rename = "require-space-after-colon", default)]
+    pub require_space_after_colon: bool,
+    /// Require the marker's casing to match the configured tag exactly: `TODO` not `todo`/`ToDo`
+    #[serde(rename = "require-canonical-case", default)]
+    pub require_canonical_case: bool,
+}
+
+/// One structured-format defect found in a marker, independent of the others
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormatIssue {
+    /// The marker's casing doesn't match the canonical tag, e.g. `todo` vs. `TODO`
+    NonCanonicalCase {
+        /// The casing actually found, e.g. `"ToDo"`
+        found: String,
+    },
+    /// No `(author)` immediately follows the marker
+    MissingAuthor,
+    /// No colon immediately follows the marker (and author, if present)
+    MissingColon,
+    /// The colon isn't followed by a space before the rest of the comment
+    MissingSpaceAfterColon,
+}
+
+impl FormatIssue {
+    /// A human-readable description suitable for a diagnostic message
+    pub fn message(&self, tag: &str) -> String {
+        match self {
+            FormatIssue::NonCanonicalCase { found } => {
+                format!("marker `{found}` should be written in canonical case as `{tag}`")
+            }
+            FormatIssue::MissingAuthor => {
+                format!("marker `{tag}` is missing a parenthesized author, e.g. `{tag}(owner):`")
+            }
+            FormatIssue::MissingColon => {
+                format!("marker `{tag}` is missing a colon, e.g. `{tag}:`")
+            }
+            FormatIssue::MissingSpaceAfterColon => {
+                format!("marker `{tag}` should have a space after its colon")
+            }
+        }
+    }
+}
+
+/// A single [`FormatIssue`], located by byte range within the comment text it was found in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatFinding {
+    /// The defect found
+    pub issue: FormatIssue,
+    /// The byte range of the offending portion within the `comment_text` passed to [`check_format`]
+    pub span: std::ops::Range<usize>,
+}
+
+/// Checks a comment already known to open with `tag` against `config`'s enabled checks
+///
+/// `comment_text` is the same untrimmed, delimiter-stripped text that would be
+/// passed to [`super::comment_tags::CommentTagsConfig::matching_tag`]; `tag`
+/// is the value that call returned. Findings are returned in the order their
+/// segments appear in the comment, not in a fixed priority order, so a
+/// consumer rendering them as a span-ordered list doesn't need to re-sort.
+pub fn check_format(
+    comment_text: &str,
+    tag: &str,
+    config: &TodoFormatConfig,
+) -> Vec<FormatFinding> {
+    let mut findings = Vec::new();
+    let leading_ws = comment_text.len() - comment_text.trim_start().len();
+    let trimmed = &comment_text[leading_ws..];
+
+    let found_marker = &trimmed[..tag.len()];
+    if config.require_canonical_case && found_marker != tag {
+        findings.push(FormatFinding {
+            issue: FormatIssue::NonCanonicalCase {
+                found: found_marker.to_string(),
+            },
+            span: leading_ws..leading_ws + tag.len(),
+        });
+    }
+
+    let mut cursor = leading_ws + tag.len();
+    let rest = &comment_text[cursor..];
+    let has_author = rest.starts_with('(');
+    if has_author {
+        if let Some(close) = rest.find(')') {
+            cursor += close + 1;
+        }
+    } else if config.require_author {
+        findings.push(FormatFinding {
+            issue: FormatIssue::MissingAuthor,
+            span: cursor..cursor,
+        });
+    }
+
+    let has_colon = comment_text[cursor..].starts_with(':');
+    if has_colon {
+        let colon_end = cursor + 1;
+        if config.require_space_after_colon && !comment_text[colon_end..].starts_with(' ') {
+            findings.push(FormatFinding {
+                issue: FormatIssue::MissingSpaceAfterColon,
+                span: cursor..colon_end,
+            });
+        }
+    } else if config.require_colon {
+        findings.push(FormatFinding {
+            issue: FormatIssue::MissingColon,
+            span: cursor..cursor,
+        });
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all_checks() -> TodoFormatConfig {
+        TodoFormatConfig {
+            require_colon: true,
+            require_author: true,
+            require_space_after_colon: true,
+            require_canonical_case: true,
+        }
+    }
+
+    #[test]
+    fn test_well_formed_marker_has_no_findings() {
+        let findings = check_format("TODO(alice): fix this", "TODO", &all_checks());
+        assert_eq!(findings, vec![]);
+    }
+
+    #[test]
+    fn test_missing_colon_is_reported() {
+        let findings = check_format("TODO(alice) fix this", "TODO", &all_checks());
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].issue, FormatIssue::MissingColon);
+    }
+
+    #[test]
+    fn test_missing_author_is_reported() {
+        let findings = check_format("TODO: fix this", "TODO", &all_checks());
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].issue, FormatIssue::MissingAuthor);
+    }
+
+    #[test]
+    fn test_missing_space_after_colon_is_reported() {
+        let findings = check_format("TODO(alice):fix this", "TODO", &all_checks());
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].issue, FormatIssue::MissingSpaceAfterColon);
+    }
+
+    #[test]
+    fn test_non_canonical_case_is_reported() {
+        let findings = check_format("todo(alice): fix this", "TODO", &all_checks());
+        assert_eq!(
+            findings[0].issue,
+            FormatIssue::NonCanonicalCase {
+                found: "todo".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_disabled_checks_produce_no_findings() {
+        let findings = check_format("todo fix this", "TODO", &TodoFormatConfig::default());
+        assert_eq!(findings, vec![]);
+    }
+
+    #[test]
+    fn test_findings_are_ordered_by_span() {
+        let findings = check_format("todo fix this", "TODO", &all_checks());
+        let spans: Vec<_> = findings.iter().map(|f| f.span.start).collect();
+        let mut sorted = spans.clone();
+        sorted.sort();
+        assert_eq!(spans, sorted);
+    }
+}
@@ -4,12 +4,205 @@
 //!
 //! This module aggregates violations by (rule_id, region) and compares
 //! actual counts against budgets from the CountsManager to determine
-//! pass/fail status.
+//! pass/fail status. A rule's budget is normally spent per violation, but a
+//! rule configured with [`SumAggregator::by_weight`] spends it per
+//! [`Violation::weight`] unit instead, so severity can outweigh cardinality.
 
 use crate::config::counts::CountsManager;
 use crate::rules::Violation;
 use crate::types::{RegionPath, RuleId};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+/// Reduces a group of violations sharing a `(rule_id, region)` key to a single `u64`
+///
+/// The associated-type-per-impl shape you'd reach for first (`type Acc;
+/// fn init() -> Acc; ...`) isn't object-safe, and [`ViolationAggregator`]
+/// needs to store a different aggregator per [`RuleId`] behind one map, so
+/// this fixes the accumulator to `u64` and takes `&self` instead of using
+/// static functions. `CountAggregator` is the default; `SumAggregator` and
+/// `MaxAggregator` let a rule reduce on a numeric field of its choosing
+/// instead of raw cardinality.
+pub trait Aggregator: Send + Sync {
+    /// The zero/identity accumulator value
+    fn init(&self) -> u64;
+    /// Folds one violation into the running accumulator
+    fn accumulate(&self, acc: &mut u64, violation: &Violation);
+    /// Reduces the final accumulator to the value compared against budget
+    fn finalize(&self, acc: u64) -> u64;
+}
+
+/// Counts violations (the aggregator `ViolationAggregator` used before this existed)
+pub struct CountAggregator;
+
+impl Aggregator for CountAggregator {
+    fn init(&self) -> u64 {
+        0
+    }
+
+    fn accumulate(&self, acc: &mut u64, _violation: &Violation) {
+        *acc += 1;
+    }
+
+    fn finalize(&self, acc: u64) -> u64 {
+        acc
+    }
+}
+
+/// Sums a numeric field read off each violation, e.g. a severity weight
+pub struct SumAggregator<F> {
+    field: F,
+}
+
+impl<F: Fn(&Violation) -> u64> SumAggregator<F> {
+    /// Creates a sum aggregator that reduces on `field`
+    pub fn new(field: F) -> Self {
+        Self { field }
+    }
+}
+
+impl SumAggregator<fn(&Violation) -> u64> {
+    /// Sums each violation's [`Violation::weight`]
+    ///
+    /// The usual way to configure a rule as severity-weighted: a budget
+    /// compared against this total is spent in weight units rather than
+    /// violation count, so one `weight: 5` violation costs as much as five
+    /// `weight: 1` ones.
+    pub fn by_weight() -> Self {
+        Self::new(|violation| violation.weight)
+    }
+}
+
+impl<F: Fn(&Violation) -> u64 + Send + Sync> Aggregator for SumAggregator<F> {
+    fn init(&self) -> u64 {
+        0
+    }
+
+    fn accumulate(&self, acc: &mut u64, violation: &Violation) {
+        *acc += (self.field)(violation);
+    }
+
+    fn finalize(&self, acc: u64) -> u64 {
+        acc
+    }
+}
+
+/// Takes the maximum of a numeric field read off each violation
+pub struct MaxAggregator<F> {
+    field: F,
+}
+
+impl<F: Fn(&Violation) -> u64> MaxAggregator<F> {
+    /// Creates a max aggregator that reduces on `field`
+    pub fn new(field: F) -> Self {
+        Self { field }
+    }
+}
+
+impl<F: Fn(&Violation) -> u64 + Send + Sync> Aggregator for MaxAggregator<F> {
+    fn init(&self) -> u64 {
+        0
+    }
+
+    fn accumulate(&self, acc: &mut u64, violation: &Violation) {
+        *acc = (*acc).max((self.field)(violation));
+    }
+
+    fn finalize(&self, acc: u64) -> u64 {
+        acc
+    }
+}
+
+/// The [`Aggregator`] used for any rule without an entry in
+/// [`ViolationAggregator`]'s aggregator map
+static DEFAULT_AGGREGATOR: CountAggregator = CountAggregator;
+
+/// Decides pass/fail for an aggregated value against its budget
+///
+/// Splits the decision out of a hardcoded `actual <= budget` the same way a
+/// capability-style access-control enforcer splits its policy/matcher from
+/// the evaluator: the inequality is data, resolved per [`RuleId`] (see
+/// [`ViolationAggregator::with_policies`]), not baked into `aggregate`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EnforcementPolicy {
+    /// `actual <= budget` passes. The default.
+    LessOrEqual,
+    /// `actual < budget` passes, forcing every change to ratchet the count down
+    StrictlyLess,
+    /// `actual <= budget * (1.0 + tolerance)` passes
+    PercentTolerance(f64),
+    /// Only `actual == budget` passes; drift in either direction fails
+    ExactMatch,
+}
+
+impl EnforcementPolicy {
+    /// Evaluates `actual` against `budget`
+    ///
+    /// Returns `(passed, over_budget)`, where `over_budget` is the magnitude
+    /// by which `actual` exceeds what this policy allows (`0` when it passes).
+    fn evaluate(&self, actual: u64, budget: u64) -> (bool, u64) {
+        match self {
+            EnforcementPolicy::LessOrEqual => {
+                if actual <= budget {
+                    (true, 0)
+                } else {
+                    (false, actual - budget)
+                }
+            }
+            EnforcementPolicy::StrictlyLess => {
+                if actual < budget {
+                    (true, 0)
+                } else {
+                    (false, actual - budget + 1)
+                }
+            }
+            EnforcementPolicy::PercentTolerance(tolerance) => {
+                let allowed = (budget as f64 * (1.0 + tolerance)).floor() as u64;
+                if actual <= allowed {
+                    (true, 0)
+                } else {
+                    (false, actual - allowed)
+                }
+            }
+            EnforcementPolicy::ExactMatch => {
+                if actual == budget {
+                    (true, 0)
+                } else {
+                    (false, actual.abs_diff(budget))
+                }
+            }
+        }
+    }
+}
+
+impl Default for EnforcementPolicy {
+    fn default() -> Self {
+        EnforcementPolicy::LessOrEqual
+    }
+}
+
+/// How seriously an over-budget rule should be treated
+///
+/// Lets a team ratchet a new rule in as [`Severity::Warning`] — reported but
+/// not blocking — before promoting it to [`Severity::Error`] once the
+/// codebase is clean, mirroring how policy engines like casbin and
+/// cfn-guard distinguish enforcement outcomes from advisory ones. Ordered
+/// most-to-least severe so sorting by `Severity` puts errors first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Over budget flips [`AggregationResult::passed`] to `false`. The default.
+    Error,
+    /// Over budget is reported but does not flip [`AggregationResult::passed`]
+    Warning,
+    /// Over budget is reported but does not flip [`AggregationResult::passed`]
+    Info,
+}
+
+impl Default for Severity {
+    fn default() -> Self {
+        Severity::Error
+    }
+}
 
 /// Result of aggregating violations against budgets
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -24,36 +217,238 @@ pub struct AggregationResult {
     pub violations_over_budget: usize,
 }
 
+impl AggregationResult {
+    /// Combines reports from several independently-run configs (e.g. one
+    /// per workspace or language) into a single report
+    ///
+    /// Each `(config_path, result)` pair tags its statuses with
+    /// `config_path` via [`RuleRegionStatus::source`], so a consumer
+    /// reading the combined stream can always tell which config a status
+    /// came from. Sums `total_violations`/`violations_over_budget` and ANDs
+    /// `passed` across every input — unlike [`crate::output::jsonl::merge`],
+    /// which reconciles shards of the *same* run key-by-key, `combine` just
+    /// concatenates independent runs side by side, so there's no per-key
+    /// budget to reconcile.
+    pub fn combine(results: Vec<(String, AggregationResult)>) -> AggregationResult {
+        let mut statuses = Vec::new();
+        let mut passed = true;
+        let mut total_violations = 0;
+        let mut violations_over_budget = 0;
+
+        for (config_path, result) in results {
+            passed = passed && result.passed;
+            total_violations += result.total_violations;
+            violations_over_budget += result.violations_over_budget;
+            for mut status in result.statuses {
+                status.source = config_path.clone();
+                statuses.push(status);
+            }
+        }
+
+        statuses.sort_by(|a, b| {
+            a.source
+                .cmp(&b.source)
+                .then_with(|| a.rule_id.as_str().cmp(b.rule_id.as_str()))
+                .then_with(|| a.region.as_str().cmp(b.region.as_str()))
+        });
+
+        AggregationResult {
+            statuses,
+            passed,
+            total_violations,
+            violations_over_budget,
+        }
+    }
+}
+
 /// Status for a single (rule, region) pair
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RuleRegionStatus {
     pub rule_id: RuleId,
     pub region: RegionPath,
+    /// The value compared against `budget`; equal to `raw_count` unless the
+    /// rule has a weighted [`Aggregator`] configured (e.g. [`SumAggregator::by_weight`])
     pub actual_count: u64,
+    /// The number of violations in this group, regardless of how they're weighted
+    pub raw_count: u64,
     pub budget: u64,
     pub passed: bool,
+    /// Magnitude by which `actual_count` exceeds what this group's
+    /// [`EnforcementPolicy`] allows; `0` when `passed` is true
+    pub over_budget: u64,
+    /// How much this group's budget being exceeded should matter; only
+    /// [`Severity::Error`] flips [`AggregationResult::passed`] to `false`
+    pub severity: Severity,
+    /// The files contributing the most violations to this group, highest
+    /// first, truncated to [`ViolationAggregator`]'s `top_k` (see
+    /// [`top_offenders`])
+    pub top_offenders: Vec<(PathBuf, u64)>,
     pub violations: Vec<Violation>,
+    /// The config file this status came from, set by [`AggregationResult::combine`]
+    /// when merging reports from several independently-run configs; empty
+    /// for a single [`ViolationAggregator::aggregate`]/[`IncrementalAggregator`] run
+    pub source: String,
 }
 
+/// Default number of worst-offender files kept in [`RuleRegionStatus::top_offenders`]
+pub(crate) const DEFAULT_TOP_K: usize = 5;
+
 /// Aggregates violations and compares against budgets
 pub struct ViolationAggregator {
     counts: CountsManager,
+    aggregators: HashMap<RuleId, Box<dyn Aggregator>>,
+    policies: HashMap<RuleId, EnforcementPolicy>,
+    severities: HashMap<RuleId, Severity>,
+    top_k: usize,
 }
 
 impl ViolationAggregator {
     /// Creates a new ViolationAggregator with the given CountsManager
+    ///
+    /// Every rule is aggregated by raw violation count (see
+    /// [`CountAggregator`]), enforced with [`EnforcementPolicy::LessOrEqual`],
+    /// and graded [`Severity::Error`]. Use [`ViolationAggregator::with_config`]
+    /// to override any of these per rule, or
+    /// [`ViolationAggregator::with_top_k`] to change how many worst
+    /// offenders are kept.
     pub fn new(counts: CountsManager) -> Self {
-        ViolationAggregator { counts }
+        Self::with_config(counts, HashMap::new(), HashMap::new(), HashMap::new())
+    }
+
+    /// Creates a ViolationAggregator with a per-rule [`Aggregator`] override
+    ///
+    /// Rules without an entry in `aggregators` still fall back to
+    /// [`CountAggregator`].
+    pub fn with_aggregators(
+        counts: CountsManager,
+        aggregators: HashMap<RuleId, Box<dyn Aggregator>>,
+    ) -> Self {
+        Self::with_config(counts, aggregators, HashMap::new(), HashMap::new())
+    }
+
+    /// Creates a ViolationAggregator with a per-rule [`EnforcementPolicy`] override
+    ///
+    /// Rules without an entry in `policies` still fall back to
+    /// [`EnforcementPolicy::LessOrEqual`].
+    pub fn with_policies(
+        counts: CountsManager,
+        policies: HashMap<RuleId, EnforcementPolicy>,
+    ) -> Self {
+        Self::with_config(counts, HashMap::new(), policies, HashMap::new())
+    }
+
+    /// Creates a ViolationAggregator with a per-rule [`Severity`] override
+    ///
+    /// Rules without an entry in `severities` still fall back to
+    /// [`Severity::Error`].
+    pub fn with_severities(counts: CountsManager, severities: HashMap<RuleId, Severity>) -> Self {
+        Self::with_config(counts, HashMap::new(), HashMap::new(), severities)
+    }
+
+    /// Creates a ViolationAggregator with per-rule [`Aggregator`],
+    /// [`EnforcementPolicy`], and [`Severity`] overrides
+    pub fn with_config(
+        counts: CountsManager,
+        aggregators: HashMap<RuleId, Box<dyn Aggregator>>,
+        policies: HashMap<RuleId, EnforcementPolicy>,
+        severities: HashMap<RuleId, Severity>,
+    ) -> Self {
+        ViolationAggregator {
+            counts,
+            aggregators,
+            policies,
+            severities,
+            top_k: DEFAULT_TOP_K,
+        }
+    }
+
+    /// Overrides how many worst-offender files [`RuleRegionStatus::top_offenders`] keeps (default 5)
+    pub fn with_top_k(mut self, top_k: usize) -> Self {
+        self.top_k = top_k;
+        self
+    }
+
+    fn aggregator_for(&self, rule_id: &RuleId) -> &dyn Aggregator {
+        self.aggregators
+            .get(rule_id)
+            .map(|aggregator| aggregator.as_ref())
+            .unwrap_or(&DEFAULT_AGGREGATOR)
+    }
+
+    fn policy_for(&self, rule_id: &RuleId) -> EnforcementPolicy {
+        self.policies.get(rule_id).copied().unwrap_or_default()
+    }
+
+    fn severity_for(&self, rule_id: &RuleId) -> Severity {
+        self.severities.get(rule_id).copied().unwrap_or_default()
+    }
+
+    /// Aggregates `violations` for one (rule, region) group into its status
+    ///
+    /// Uses `region_budget` when given (the region's own configured budget);
+    /// otherwise the budget is looked up from the first violation's file
+    /// path, which the CountsManager resolves via region inheritance.
+    fn build_status(
+        &self,
+        rule_id: RuleId,
+        region: RegionPath,
+        violations: Vec<Violation>,
+        region_budget: Option<u64>,
+    ) -> RuleRegionStatus {
+        let raw_count = violations.len() as u64;
+
+        let aggregator = self.aggregator_for(&rule_id);
+        let mut acc = aggregator.init();
+        for violation in &violations {
+            aggregator.accumulate(&mut acc, violation);
+        }
+        let actual_count = aggregator.finalize(acc);
+
+        let budget = if let Some(region_budget) = region_budget {
+            region_budget
+        } else if let Some(first_violation) = violations.first() {
+            self.counts.get_budget(&rule_id, &first_violation.file)
+        } else {
+            // This shouldn't happen since we only build statuses from non-empty groups
+            0
+        };
+
+        let (passed, over_budget) = self.policy_for(&rule_id).evaluate(actual_count, budget);
+        let severity = self.severity_for(&rule_id);
+        let top_offenders = top_offenders(&violations, self.top_k);
+
+        RuleRegionStatus {
+            rule_id,
+            region,
+            actual_count,
+            raw_count,
+            budget,
+            passed,
+            over_budget,
+            severity,
+            top_offenders,
+            violations,
+            source: String::new(),
+        }
     }
 
     /// Aggregate violations and check against budgets
     ///
     /// Algorithm:
     /// 1. Group violations by (rule_id, region_path)
-    /// 2. For each group, count violations
+    /// 2. For each group, aggregate violations (see [`Aggregator`])
     /// 3. Look up budget from CountsManager using the first file path in the group
-    /// 4. Compare count vs budget: if actual > budget, status is FAIL
-    /// 5. Overall pass = all rule/regions pass
+    /// 4. Compare aggregate vs budget via the rule's [`EnforcementPolicy`]
+    ///    (see [`ViolationAggregator::with_policies`]); if the policy rejects it, status is FAIL
+    /// 5. Roll each group's violations up into any *proper* ancestor region
+    ///    (see [`region_ancestors`]) that has its own configured budget,
+    ///    merging into that ancestor's own direct status if one already
+    ///    exists and checking the total against that ancestor's own budget,
+    ///    so a budget on `src` can cap the combined total across `src/a`,
+    ///    `src/b`, etc.
+    /// 6. Overall pass = every [`Severity::Error`] rule/region passes;
+    ///    [`Severity::Warning`]/[`Severity::Info`] rules are still reported
+    ///    over budget but don't flip the overall result
     pub fn aggregate(&self, violations: Vec<Violation>) -> AggregationResult {
         // Group violations by (rule_id, region)
         let mut groups: HashMap<(RuleId, RegionPath), Vec<Violation>> = HashMap::new();
@@ -63,40 +458,51 @@ impl ViolationAggregator {
             groups.entry(key).or_default().push(violation);
         }
 
-        // Calculate status for each group
-        let mut statuses = Vec::new();
-        let mut total_violations = 0;
-        let mut violations_over_budget = 0;
-        let mut all_passed = true;
-
-        for ((rule_id, region), group_violations) in groups.into_iter() {
-            let actual_count = group_violations.len() as u64;
-            total_violations += actual_count as usize;
+        let total_violations: usize = groups.values().map(Vec::len).sum();
+
+        // Only roll up into regions that are configured with their own
+        // budget; an unconfigured ancestor has no budget to cap against, so
+        // there's nothing meaningful to roll up into.
+        let configured_regions: HashMap<(RuleId, RegionPath), u64> = self
+            .counts
+            .entries()
+            .map(|(rule_id, region, budget)| ((rule_id, region), budget))
+            .collect();
+
+        let mut rollups: HashMap<(RuleId, RegionPath), Vec<Violation>> = HashMap::new();
+        for ((rule_id, region), group_violations) in &groups {
+            for ancestor in region_ancestors(region) {
+                let key = (rule_id.clone(), ancestor);
+                if configured_regions.contains_key(&key) {
+                    rollups
+                        .entry(key)
+                        .or_default()
+                        .extend(group_violations.iter().cloned());
+                }
+            }
+        }
 
-            // Look up budget using the file path from the first violation
-            // The CountsManager uses the file path for inheritance lookup
-            let budget = if let Some(first_violation) = group_violations.first() {
-                self.counts.get_budget(&rule_id, &first_violation.file)
+        let mut statuses: Vec<RuleRegionStatus> = groups
+            .into_iter()
+            .map(|((rule_id, region), group_violations)| {
+                self.build_status(rule_id, region, group_violations, None)
+            })
+            .collect();
+
+        for ((rule_id, region), rolled_up) in rollups {
+            let region_budget = configured_regions
+                .get(&(rule_id.clone(), region.clone()))
+                .copied();
+            if let Some(existing) = statuses
+                .iter_mut()
+                .find(|status| status.rule_id == rule_id && status.region == region)
+            {
+                let mut combined = std::mem::take(&mut existing.violations);
+                combined.extend(rolled_up);
+                *existing = self.build_status(rule_id, region, combined, region_budget);
             } else {
-                // This shouldn't happen since we only create groups with violations
-                0
-            };
-
-            let passed = actual_count <= budget;
-
-            if !passed {
-                all_passed = false;
-                violations_over_budget += (actual_count - budget) as usize;
+                statuses.push(self.build_status(rule_id, region, rolled_up, region_budget));
             }
-
-            statuses.push(RuleRegionStatus {
-                rule_id,
-                region,
-                actual_count,
-                budget,
-                passed,
-                violations: group_violations,
-            });
         }
 
         // Sort statuses for deterministic output
@@ -107,6 +513,14 @@ impl ViolationAggregator {
                 .then_with(|| a.region.as_str().cmp(b.region.as_str()))
         });
 
+        let violations_over_budget = statuses
+            .iter()
+            .map(|status| status.over_budget as usize)
+            .sum();
+        let all_passed = statuses
+            .iter()
+            .all(|status| status.passed || status.severity != Severity::Error);
+
         AggregationResult {
             statuses,
             passed: all_passed,
@@ -116,12 +530,299 @@ impl ViolationAggregator {
     }
 }
 
+/// Returns `region`'s proper ancestors, nearest first, ending at the root region `"."`
+///
+/// Splits on `/` and drops one path segment at a time, so `region` itself is
+/// never included — only its strict ancestors, which is what lets
+/// [`ViolationAggregator::aggregate`] roll a group's violations up the tree
+/// without folding a region into itself.
+fn region_ancestors(region: &RegionPath) -> Vec<RegionPath> {
+    let path = region.as_str();
+    if path == "." {
+        return Vec::new();
+    }
+
+    let mut segments: Vec<&str> = path.split('/').collect();
+    let mut ancestors = Vec::new();
+    while segments.len() > 1 {
+        segments.pop();
+        ancestors.push(RegionPath::new(segments.join("/")));
+    }
+    ancestors.push(RegionPath::new("."));
+    ancestors
+}
+
+/// Reduces `violations` to the `k` files contributing the most of them
+///
+/// Sorted descending by count, ties broken by path for determinism. Rather
+/// than handing the caller the full violation list to sort through, this
+/// keeps only the top-k worst offenders a reviewer actually needs first.
+pub(crate) fn top_offenders(violations: &[Violation], k: usize) -> Vec<(PathBuf, u64)> {
+    let mut counts: HashMap<PathBuf, u64> = HashMap::new();
+    for violation in violations {
+        *counts.entry(violation.file.clone()).or_insert(0) += 1;
+    }
+
+    let mut offenders: Vec<(PathBuf, u64)> = counts.into_iter().collect();
+    offenders.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    offenders.truncate(k);
+    offenders
+}
+
+/// Per-(rule, region) state kept live by [`IncrementalAggregator`]
+///
+/// Violations are kept keyed by the file that contributed them so
+/// [`IncrementalAggregator::update_file`] can drop and replace one file's
+/// share without touching any other file's.
+struct GroupState {
+    violations_by_file: HashMap<PathBuf, Vec<Violation>>,
+    budget: u64,
+    actual_count: u64,
+    raw_count: u64,
+    passed: bool,
+    over_budget: u64,
+    severity: Severity,
+}
+
+impl GroupState {
+    fn new(budget: u64, severity: Severity) -> Self {
+        GroupState {
+            violations_by_file: HashMap::new(),
+            budget,
+            actual_count: 0,
+            raw_count: 0,
+            passed: true,
+            over_budget: 0,
+            severity,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.violations_by_file.values().all(|v| v.is_empty())
+    }
+
+    fn recompute(&mut self, aggregator: &dyn Aggregator, policy: EnforcementPolicy) {
+        let mut acc = aggregator.init();
+        let mut raw_count = 0u64;
+        for violations in self.violations_by_file.values() {
+            for violation in violations {
+                aggregator.accumulate(&mut acc, violation);
+                raw_count += 1;
+            }
+        }
+        self.actual_count = aggregator.finalize(acc);
+        self.raw_count = raw_count;
+        let (passed, over_budget) = policy.evaluate(self.actual_count, self.budget);
+        self.passed = passed;
+        self.over_budget = over_budget;
+    }
+
+    fn to_status(&self, rule_id: RuleId, region: RegionPath, top_k: usize) -> RuleRegionStatus {
+        let violations: Vec<Violation> = self
+            .violations_by_file
+            .values()
+            .flatten()
+            .cloned()
+            .collect();
+
+        RuleRegionStatus {
+            rule_id,
+            region,
+            actual_count: self.actual_count,
+            raw_count: self.raw_count,
+            budget: self.budget,
+            passed: self.passed,
+            over_budget: self.over_budget,
+            severity: self.severity,
+            top_offenders: top_offenders(&violations, top_k),
+            violations,
+            source: String::new(),
+        }
+    }
+}
+
+/// Aggregates violations incrementally, for editor/watch/LSP loops that re-lint a handful of files at a time
+///
+/// Unlike [`ViolationAggregator::aggregate`], which rebuilds its grouping
+/// from scratch every call, this keeps a live [`GroupState`] per (rule,
+/// region) and a reverse index from file to the groups it contributes to.
+/// [`IncrementalAggregator::update_file`] only recomputes the groups a
+/// changed file actually touches, so the cost of re-linting one file stays
+/// proportional to that file, not to the whole repo.
+pub struct IncrementalAggregator {
+    counts: CountsManager,
+    aggregators: HashMap<RuleId, Box<dyn Aggregator>>,
+    policies: HashMap<RuleId, EnforcementPolicy>,
+    severities: HashMap<RuleId, Severity>,
+    top_k: usize,
+    groups: HashMap<(RuleId, RegionPath), GroupState>,
+    files: HashMap<PathBuf, HashSet<(RuleId, RegionPath)>>,
+}
+
+impl IncrementalAggregator {
+    /// Creates a new IncrementalAggregator with the given CountsManager
+    pub fn new(counts: CountsManager) -> Self {
+        Self::with_config(counts, HashMap::new(), HashMap::new(), HashMap::new())
+    }
+
+    /// Creates an IncrementalAggregator with a per-rule [`Aggregator`] override
+    ///
+    /// See [`ViolationAggregator::with_aggregators`].
+    pub fn with_aggregators(
+        counts: CountsManager,
+        aggregators: HashMap<RuleId, Box<dyn Aggregator>>,
+    ) -> Self {
+        Self::with_config(counts, aggregators, HashMap::new(), HashMap::new())
+    }
+
+    /// Creates an IncrementalAggregator with a per-rule [`EnforcementPolicy`] override
+    ///
+    /// See [`ViolationAggregator::with_policies`].
+    pub fn with_policies(
+        counts: CountsManager,
+        policies: HashMap<RuleId, EnforcementPolicy>,
+    ) -> Self {
+        Self::with_config(counts, HashMap::new(), policies, HashMap::new())
+    }
+
+    /// Creates an IncrementalAggregator with a per-rule [`Severity`] override
+    ///
+    /// See [`ViolationAggregator::with_severities`].
+    pub fn with_severities(counts: CountsManager, severities: HashMap<RuleId, Severity>) -> Self {
+        Self::with_config(counts, HashMap::new(), HashMap::new(), severities)
+    }
+
+    /// Creates an IncrementalAggregator with per-rule [`Aggregator`],
+    /// [`EnforcementPolicy`], and [`Severity`] overrides
+    pub fn with_config(
+        counts: CountsManager,
+        aggregators: HashMap<RuleId, Box<dyn Aggregator>>,
+        policies: HashMap<RuleId, EnforcementPolicy>,
+        severities: HashMap<RuleId, Severity>,
+    ) -> Self {
+        IncrementalAggregator {
+            counts,
+            aggregators,
+            policies,
+            severities,
+            top_k: DEFAULT_TOP_K,
+            groups: HashMap::new(),
+            files: HashMap::new(),
+        }
+    }
+
+    /// Overrides how many worst-offender files [`RuleRegionStatus::top_offenders`] keeps (default 5)
+    pub fn with_top_k(mut self, top_k: usize) -> Self {
+        self.top_k = top_k;
+        self
+    }
+
+    /// Replaces `path`'s contribution to the aggregate and recomputes only the affected groups
+    ///
+    /// Returns the [`RuleRegionStatus`] for every (rule, region) group that
+    /// `path` previously or now belongs to, along with the new overall
+    /// `passed` across every known group.
+    pub fn update_file(
+        &mut self,
+        path: PathBuf,
+        new_violations: Vec<Violation>,
+    ) -> (Vec<RuleRegionStatus>, bool) {
+        let mut touched = self.files.remove(&path).unwrap_or_default();
+
+        for key in &touched {
+            if let Some(group) = self.groups.get_mut(key) {
+                group.violations_by_file.remove(&path);
+            }
+        }
+
+        let mut by_group: HashMap<(RuleId, RegionPath), Vec<Violation>> = HashMap::new();
+        for violation in new_violations {
+            let key = (violation.rule_id.clone(), violation.region.clone());
+            by_group.entry(key).or_default().push(violation);
+        }
+
+        let mut current_keys = HashSet::new();
+        for (key, violations) in by_group {
+            current_keys.insert(key.clone());
+            touched.insert(key.clone());
+
+            let budget = self.counts.get_budget(&key.0, &violations[0].file);
+            let severity = self.severities.get(&key.0).copied().unwrap_or_default();
+            let group = self
+                .groups
+                .entry(key)
+                .or_insert_with(|| GroupState::new(budget, severity));
+            group.violations_by_file.insert(path.clone(), violations);
+        }
+
+        if !current_keys.is_empty() {
+            self.files.insert(path, current_keys);
+        }
+
+        let aggregators = &self.aggregators;
+        let policies = &self.policies;
+        let mut changed = Vec::new();
+        for key in touched {
+            let is_empty = {
+                let Some(group) = self.groups.get_mut(&key) else {
+                    continue;
+                };
+
+                let aggregator = aggregators
+                    .get(&key.0)
+                    .map(|a| a.as_ref())
+                    .unwrap_or(&DEFAULT_AGGREGATOR);
+                let policy = policies.get(&key.0).copied().unwrap_or_default();
+                group.recompute(aggregator, policy);
+                group.is_empty()
+            };
+
+            let status = if is_empty {
+                self.groups
+                    .remove(&key)
+                    .expect("just looked up above")
+                    .to_status(key.0, key.1, self.top_k)
+            } else {
+                self.groups
+                    .get(&key)
+                    .expect("just looked up above")
+                    .to_status(key.0.clone(), key.1.clone(), self.top_k)
+            };
+            changed.push(status);
+        }
+
+        changed.sort_by(|a, b| {
+            a.rule_id
+                .as_str()
+                .cmp(b.rule_id.as_str())
+                .then_with(|| a.region.as_str().cmp(b.region.as_str()))
+        });
+
+        let overall_passed = self
+            .groups
+            .values()
+            .all(|group| group.passed || group.severity != Severity::Error);
+
+        (changed, overall_passed)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::path::PathBuf;
 
     fn create_test_violation(rule_id: &str, file_path: &str, region: &str, line: u32) -> Violation {
+        create_test_violation_weighted(rule_id, file_path, region, line, 1)
+    }
+
+    fn create_test_violation_weighted(
+        rule_id: &str,
+        file_path: &str,
+        region: &str,
+        line: u32,
+        weight: u64,
+    ) -> Violation {
         Violation {
             rule_id: RuleId::new(rule_id).unwrap(),
             file: PathBuf::from(file_path),
@@ -132,6 +833,7 @@ mod tests {
             snippet: "test".to_string(),
             message: "Test violation".to_string(),
             region: RegionPath::new(region),
+            weight,
         }
     }
 
@@ -543,22 +1245,678 @@ mod tests {
     }
 
     #[test]
-    fn test_rule_region_status_derives() {
-        let status = RuleRegionStatus {
-            rule_id: RuleId::new("test").unwrap(),
-            region: RegionPath::new("src"),
-            actual_count: 5,
-            budget: 10,
-            passed: true,
-            violations: vec![],
-        };
+    fn test_sum_aggregator_reduces_by_field_instead_of_count() {
+        let mut counts = CountsManager::new();
+        counts.set_count(
+            &RuleId::new("no-unwrap").unwrap(),
+            &RegionPath::new("src"),
+            15,
+        );
 
-        // Test clone
-        let cloned = status.clone();
-        assert_eq!(status, cloned);
+        let rule_id = RuleId::new("no-unwrap").unwrap();
+        let mut aggregators: HashMap<RuleId, Box<dyn Aggregator>> = HashMap::new();
+        aggregators.insert(
+            rule_id.clone(),
+            Box::new(SumAggregator::new(|v: &Violation| v.line as u64)),
+        );
 
-        // Test debug
-        let debug_str = format!("{:?}", status);
-        assert!(debug_str.contains("RuleRegionStatus"));
+        let aggregator = ViolationAggregator::with_aggregators(counts, aggregators);
+
+        // Two violations with lines 10 and 20 sum to 30, over the budget of
+        // 15, even though there are only 2 violations (well within a
+        // count-based budget of 15).
+        let violations = vec![
+            create_test_violation("no-unwrap", "src/main.rs", "src", 10),
+            create_test_violation("no-unwrap", "src/lib.rs", "src", 20),
+        ];
+
+        let result = aggregator.aggregate(violations);
+
+        assert_eq!(result.total_violations, 2);
+        let status = &result.statuses[0];
+        assert_eq!(status.actual_count, 30);
+        assert!(!status.passed);
+    }
+
+    #[test]
+    fn test_max_aggregator_reduces_to_largest_field_value() {
+        let mut counts = CountsManager::new();
+        counts.set_count(
+            &RuleId::new("no-unwrap").unwrap(),
+            &RegionPath::new("src"),
+            15,
+        );
+
+        let rule_id = RuleId::new("no-unwrap").unwrap();
+        let mut aggregators: HashMap<RuleId, Box<dyn Aggregator>> = HashMap::new();
+        aggregators.insert(
+            rule_id,
+            Box::new(MaxAggregator::new(|v: &Violation| v.line as u64)),
+        );
+
+        let aggregator = ViolationAggregator::with_aggregators(counts, aggregators);
+
+        let violations = vec![
+            create_test_violation("no-unwrap", "src/main.rs", "src", 10),
+            create_test_violation("no-unwrap", "src/lib.rs", "src", 20),
+        ];
+
+        let result = aggregator.aggregate(violations);
+
+        let status = &result.statuses[0];
+        assert_eq!(status.actual_count, 20);
+        assert!(status.passed);
+    }
+
+    #[test]
+    fn test_rules_without_an_override_still_use_count_aggregator() {
+        let mut counts = CountsManager::new();
+        counts.set_count(&RuleId::new("no-todo").unwrap(), &RegionPath::new("src"), 5);
+
+        // An override is registered for a different rule; "no-todo" should
+        // still fall back to plain counting.
+        let mut aggregators: HashMap<RuleId, Box<dyn Aggregator>> = HashMap::new();
+        aggregators.insert(
+            RuleId::new("no-unwrap").unwrap(),
+            Box::new(SumAggregator::new(|v: &Violation| v.line as u64)),
+        );
+
+        let aggregator = ViolationAggregator::with_aggregators(counts, aggregators);
+
+        let violations = vec![
+            create_test_violation("no-todo", "src/main.rs", "src", 100),
+            create_test_violation("no-todo", "src/lib.rs", "src", 200),
+        ];
+
+        let result = aggregator.aggregate(violations);
+
+        let status = &result.statuses[0];
+        assert_eq!(status.actual_count, 2);
+        assert!(status.passed);
+    }
+
+    #[test]
+    fn test_weighted_budget_fails_on_accumulated_weight_not_violation_count() {
+        let mut counts = CountsManager::new();
+        counts.set_count(
+            &RuleId::new("no-unwrap").unwrap(),
+            &RegionPath::new("src"),
+            4,
+        );
+
+        let mut aggregators: HashMap<RuleId, Box<dyn Aggregator>> = HashMap::new();
+        aggregators.insert(
+            RuleId::new("no-unwrap").unwrap(),
+            Box::new(SumAggregator::by_weight()),
+        );
+        let aggregator = ViolationAggregator::with_aggregators(counts, aggregators);
+
+        // One high-severity violation (weight 5) alone blows a budget of 4,
+        // even though there's a single violation in the group.
+        let violations = vec![create_test_violation_weighted(
+            "no-unwrap",
+            "src/main.rs",
+            "src",
+            10,
+            5,
+        )];
+
+        let result = aggregator.aggregate(violations);
+
+        let status = &result.statuses[0];
+        assert_eq!(status.raw_count, 1);
+        assert_eq!(status.actual_count, 5);
+        assert!(!status.passed);
+        assert_eq!(result.violations_over_budget, 1);
+    }
+
+    #[test]
+    fn test_raw_count_tracks_cardinality_independent_of_weighting() {
+        let counts = CountsManager::new();
+        let aggregator = ViolationAggregator::new(counts);
+
+        let violations = vec![
+            create_test_violation_weighted("no-unwrap", "src/main.rs", "src", 10, 3),
+            create_test_violation_weighted("no-unwrap", "src/lib.rs", "src", 20, 7),
+        ];
+
+        let result = aggregator.aggregate(violations);
+
+        // Unweighted (default) aggregation still counts violations, not weight.
+        let status = &result.statuses[0];
+        assert_eq!(status.raw_count, 2);
+        assert_eq!(status.actual_count, 2);
+    }
+
+    #[test]
+    fn test_rule_region_status_derives() {
+        let status = RuleRegionStatus {
+            rule_id: RuleId::new("test").unwrap(),
+            region: RegionPath::new("src"),
+            actual_count: 5,
+            raw_count: 5,
+            budget: 10,
+            passed: true,
+            over_budget: 0,
+            severity: Severity::Error,
+            top_offenders: vec![],
+            violations: vec![],
+            source: String::new(),
+        };
+
+        // Test clone
+        let cloned = status.clone();
+        assert_eq!(status, cloned);
+
+        // Test debug
+        let debug_str = format!("{:?}", status);
+        assert!(debug_str.contains("RuleRegionStatus"));
+    }
+
+    #[test]
+    fn test_incremental_update_file_reports_over_budget_status() {
+        let mut counts = CountsManager::new();
+        counts.set_count(
+            &RuleId::new("no-unwrap").unwrap(),
+            &RegionPath::new("src"),
+            1,
+        );
+        let mut aggregator = IncrementalAggregator::new(counts);
+
+        let violations = vec![
+            create_test_violation("no-unwrap", "src/main.rs", "src", 10),
+            create_test_violation("no-unwrap", "src/main.rs", "src", 20),
+        ];
+
+        let (changed, overall_passed) =
+            aggregator.update_file(PathBuf::from("src/main.rs"), violations);
+
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[0].raw_count, 2);
+        assert!(!changed[0].passed);
+        assert!(!overall_passed);
+    }
+
+    #[test]
+    fn test_incremental_update_file_replaces_prior_contribution() {
+        let counts = CountsManager::new();
+        let mut aggregator = IncrementalAggregator::new(counts);
+
+        aggregator.update_file(
+            PathBuf::from("src/main.rs"),
+            vec![
+                create_test_violation("no-unwrap", "src/main.rs", "src", 10),
+                create_test_violation("no-unwrap", "src/main.rs", "src", 20),
+                create_test_violation("no-unwrap", "src/main.rs", "src", 30),
+            ],
+        );
+
+        // Re-linting the same file with fewer violations should replace, not
+        // add to, its prior contribution.
+        let (changed, overall_passed) = aggregator.update_file(
+            PathBuf::from("src/main.rs"),
+            vec![create_test_violation("no-unwrap", "src/main.rs", "src", 10)],
+        );
+
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[0].raw_count, 1);
+        // No budget is configured, so the default budget of 0 still fails
+        assert!(!overall_passed);
+    }
+
+    #[test]
+    fn test_incremental_update_file_clears_group_once_file_is_clean() {
+        let mut counts = CountsManager::new();
+        counts.set_count(
+            &RuleId::new("no-unwrap").unwrap(),
+            &RegionPath::new("src"),
+            0,
+        );
+        let mut aggregator = IncrementalAggregator::new(counts);
+
+        aggregator.update_file(
+            PathBuf::from("src/main.rs"),
+            vec![create_test_violation("no-unwrap", "src/main.rs", "src", 10)],
+        );
+
+        let (changed, overall_passed) =
+            aggregator.update_file(PathBuf::from("src/main.rs"), vec![]);
+
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[0].raw_count, 0);
+        assert!(changed[0].passed);
+        assert!(overall_passed);
+    }
+
+    #[test]
+    fn test_incremental_update_file_leaves_other_files_in_the_same_group_untouched() {
+        let mut counts = CountsManager::new();
+        counts.set_count(
+            &RuleId::new("no-unwrap").unwrap(),
+            &RegionPath::new("src"),
+            10,
+        );
+        let mut aggregator = IncrementalAggregator::new(counts);
+
+        aggregator.update_file(
+            PathBuf::from("src/main.rs"),
+            vec![create_test_violation("no-unwrap", "src/main.rs", "src", 10)],
+        );
+        aggregator.update_file(
+            PathBuf::from("src/lib.rs"),
+            vec![create_test_violation("no-unwrap", "src/lib.rs", "src", 5)],
+        );
+
+        // Re-linting main.rs with no violations must not drop lib.rs's
+        // still-live contribution to the same (rule, region) group.
+        let (changed, overall_passed) =
+            aggregator.update_file(PathBuf::from("src/main.rs"), vec![]);
+
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[0].raw_count, 1);
+        assert!(overall_passed);
+    }
+
+    #[test]
+    fn test_rollup_caps_combined_total_across_sibling_subdirectories() {
+        let mut counts = CountsManager::new();
+        let rule_id = RuleId::new("no-unwrap").unwrap();
+        counts.set_count(&rule_id, &RegionPath::new("src"), 2);
+        let aggregator = ViolationAggregator::new(counts);
+
+        let violations = vec![
+            create_test_violation("no-unwrap", "src/a/main.rs", "src/a", 1),
+            create_test_violation("no-unwrap", "src/b/main.rs", "src/b", 2),
+            create_test_violation("no-unwrap", "src/b/lib.rs", "src/b", 3),
+        ];
+
+        let result = aggregator.aggregate(violations);
+
+        let rolled_up = result
+            .statuses
+            .iter()
+            .find(|s| s.region.as_str() == "src")
+            .expect("src should have a rolled-up status");
+
+        // 1 violation under src/a + 2 under src/b = 3, over the budget of 2
+        // configured on src, even though no single subdirectory is over budget.
+        assert_eq!(rolled_up.actual_count, 3);
+        assert!(!rolled_up.passed);
+        assert!(!result.passed);
+    }
+
+    #[test]
+    fn test_rollup_merges_into_ancestors_own_direct_violations() {
+        let mut counts = CountsManager::new();
+        let rule_id = RuleId::new("no-unwrap").unwrap();
+        counts.set_count(&rule_id, &RegionPath::new("src"), 1);
+        let aggregator = ViolationAggregator::new(counts);
+
+        let violations = vec![
+            create_test_violation("no-unwrap", "src/main.rs", "src", 1),
+            create_test_violation("no-unwrap", "src/a/lib.rs", "src/a", 2),
+        ];
+
+        let result = aggregator.aggregate(violations);
+
+        // src's own status must combine its direct violation with the one
+        // rolled up from src/a, not produce two separate "src" entries.
+        let src_statuses: Vec<_> = result
+            .statuses
+            .iter()
+            .filter(|s| s.region.as_str() == "src")
+            .collect();
+        assert_eq!(src_statuses.len(), 1);
+        assert_eq!(src_statuses[0].actual_count, 2);
+        assert!(!src_statuses[0].passed);
+    }
+
+    #[test]
+    fn test_rollup_skips_ancestors_without_a_configured_budget() {
+        let counts = CountsManager::new();
+        let aggregator = ViolationAggregator::new(counts);
+
+        let violations = vec![create_test_violation(
+            "no-unwrap",
+            "src/a/main.rs",
+            "src/a",
+            1,
+        )];
+
+        let result = aggregator.aggregate(violations);
+
+        // "src" and "." have no configured budget, so only the leaf group
+        // for "src/a" should appear.
+        assert_eq!(result.statuses.len(), 1);
+        assert_eq!(result.statuses[0].region.as_str(), "src/a");
+    }
+
+    #[test]
+    fn test_region_ancestors_walks_to_root() {
+        assert_eq!(
+            region_ancestors(&RegionPath::new("src/a/b"))
+                .iter()
+                .map(|r| r.as_str().to_string())
+                .collect::<Vec<_>>(),
+            vec!["src/a".to_string(), "src".to_string(), ".".to_string()]
+        );
+        assert!(region_ancestors(&RegionPath::new(".")).is_empty());
+    }
+
+    #[test]
+    fn test_strictly_less_policy_fails_on_exact_budget_match() {
+        let mut counts = CountsManager::new();
+        let rule_id = RuleId::new("no-unwrap").unwrap();
+        counts.set_count(&rule_id, &RegionPath::new("src"), 3);
+
+        let mut policies = HashMap::new();
+        policies.insert(rule_id, EnforcementPolicy::StrictlyLess);
+        let aggregator = ViolationAggregator::with_policies(counts, policies);
+
+        let violations = vec![
+            create_test_violation("no-unwrap", "src/a.rs", "src", 1),
+            create_test_violation("no-unwrap", "src/b.rs", "src", 2),
+            create_test_violation("no-unwrap", "src/c.rs", "src", 3),
+        ];
+
+        let result = aggregator.aggregate(violations);
+
+        // LessOrEqual would pass at exactly 3 == budget; StrictlyLess must not.
+        assert!(!result.statuses[0].passed);
+        assert_eq!(result.statuses[0].over_budget, 1);
+    }
+
+    #[test]
+    fn test_percent_tolerance_policy_allows_budget_overshoot_within_tolerance() {
+        let mut counts = CountsManager::new();
+        let rule_id = RuleId::new("no-unwrap").unwrap();
+        counts.set_count(&rule_id, &RegionPath::new("src"), 10);
+
+        let mut policies = HashMap::new();
+        policies.insert(rule_id, EnforcementPolicy::PercentTolerance(0.2));
+        let aggregator = ViolationAggregator::with_policies(counts, policies);
+
+        // 11 is within 10 * 1.2 = 12, so it passes despite exceeding the raw budget.
+        let violations: Vec<_> = (0..11)
+            .map(|i| create_test_violation("no-unwrap", "src/a.rs", "src", i))
+            .collect();
+
+        let result = aggregator.aggregate(violations);
+
+        assert!(result.statuses[0].passed);
+    }
+
+    #[test]
+    fn test_exact_match_policy_fails_when_under_budget() {
+        let mut counts = CountsManager::new();
+        let rule_id = RuleId::new("no-unwrap").unwrap();
+        counts.set_count(&rule_id, &RegionPath::new("src"), 5);
+
+        let mut policies = HashMap::new();
+        policies.insert(rule_id, EnforcementPolicy::ExactMatch);
+        let aggregator = ViolationAggregator::with_policies(counts, policies);
+
+        let violations = vec![create_test_violation("no-unwrap", "src/a.rs", "src", 1)];
+
+        let result = aggregator.aggregate(violations);
+
+        assert!(!result.statuses[0].passed);
+        assert_eq!(result.statuses[0].over_budget, 4);
+    }
+
+    #[test]
+    fn test_default_policy_remains_less_or_equal() {
+        assert_eq!(EnforcementPolicy::default(), EnforcementPolicy::LessOrEqual);
+
+        let mut counts = CountsManager::new();
+        counts.set_count(
+            &RuleId::new("no-unwrap").unwrap(),
+            &RegionPath::new("src"),
+            1,
+        );
+        let aggregator = ViolationAggregator::new(counts);
+
+        let violations = vec![create_test_violation("no-unwrap", "src/a.rs", "src", 1)];
+        let result = aggregator.aggregate(violations);
+
+        assert!(result.statuses[0].passed);
+    }
+
+    #[test]
+    fn test_top_offenders_ranks_files_by_violation_count_descending() {
+        let counts = CountsManager::new();
+        let aggregator = ViolationAggregator::new(counts);
+
+        let violations = vec![
+            create_test_violation("no-unwrap", "src/hot.rs", "src", 1),
+            create_test_violation("no-unwrap", "src/hot.rs", "src", 2),
+            create_test_violation("no-unwrap", "src/hot.rs", "src", 3),
+            create_test_violation("no-unwrap", "src/warm.rs", "src", 4),
+            create_test_violation("no-unwrap", "src/warm.rs", "src", 5),
+            create_test_violation("no-unwrap", "src/cold.rs", "src", 6),
+        ];
+
+        let result = aggregator.aggregate(violations);
+
+        assert_eq!(
+            result.statuses[0].top_offenders,
+            vec![
+                (PathBuf::from("src/hot.rs"), 3),
+                (PathBuf::from("src/warm.rs"), 2),
+                (PathBuf::from("src/cold.rs"), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_top_offenders_breaks_ties_by_path_for_determinism() {
+        let counts = CountsManager::new();
+        let aggregator = ViolationAggregator::new(counts);
+
+        let violations = vec![
+            create_test_violation("no-unwrap", "src/b.rs", "src", 1),
+            create_test_violation("no-unwrap", "src/a.rs", "src", 2),
+        ];
+
+        let result = aggregator.aggregate(violations);
+
+        assert_eq!(
+            result.statuses[0].top_offenders,
+            vec![
+                (PathBuf::from("src/a.rs"), 1),
+                (PathBuf::from("src/b.rs"), 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_top_offenders_truncates_to_configured_k() {
+        let counts = CountsManager::new();
+        let aggregator = ViolationAggregator::new(counts).with_top_k(2);
+
+        let violations = vec![
+            create_test_violation("no-unwrap", "src/a.rs", "src", 1),
+            create_test_violation("no-unwrap", "src/b.rs", "src", 2),
+            create_test_violation("no-unwrap", "src/c.rs", "src", 3),
+        ];
+
+        let result = aggregator.aggregate(violations);
+
+        assert_eq!(result.statuses[0].top_offenders.len(), 2);
+    }
+
+    #[test]
+    fn test_incremental_aggregator_also_reports_top_offenders() {
+        let counts = CountsManager::new();
+        let mut aggregator = IncrementalAggregator::new(counts).with_top_k(1);
+
+        let (changed, _) = aggregator.update_file(
+            PathBuf::from("src/main.rs"),
+            vec![
+                create_test_violation("no-unwrap", "src/main.rs", "src", 1),
+                create_test_violation("no-unwrap", "src/main.rs", "src", 2),
+            ],
+        );
+
+        assert_eq!(
+            changed[0].top_offenders,
+            vec![(PathBuf::from("src/main.rs"), 2)]
+        );
+    }
+
+    #[test]
+    fn test_default_severity_is_error() {
+        assert_eq!(Severity::default(), Severity::Error);
+    }
+
+    #[test]
+    fn test_severity_orders_most_severe_first() {
+        assert!(Severity::Error < Severity::Warning);
+        assert!(Severity::Warning < Severity::Info);
+    }
+
+    #[test]
+    fn test_warning_severity_over_budget_does_not_fail_overall_result() {
+        let mut counts = CountsManager::new();
+        let rule_id = RuleId::new("no-unwrap").unwrap();
+        counts.set_count(&rule_id, &RegionPath::new("src"), 1);
+
+        let mut severities = HashMap::new();
+        severities.insert(rule_id, Severity::Warning);
+        let aggregator = ViolationAggregator::with_severities(counts, severities);
+
+        let violations = vec![
+            create_test_violation("no-unwrap", "src/a.rs", "src", 1),
+            create_test_violation("no-unwrap", "src/b.rs", "src", 2),
+        ];
+
+        let result = aggregator.aggregate(violations);
+
+        // The rule/region itself still reports that it's over budget...
+        assert!(!result.statuses[0].passed);
+        assert_eq!(result.statuses[0].severity, Severity::Warning);
+        // ...but a Warning doesn't flip the overall result.
+        assert!(result.passed);
+    }
+
+    #[test]
+    fn test_error_severity_over_budget_fails_overall_result() {
+        let mut counts = CountsManager::new();
+        let rule_id = RuleId::new("no-unwrap").unwrap();
+        counts.set_count(&rule_id, &RegionPath::new("src"), 1);
+
+        let mut severities = HashMap::new();
+        severities.insert(rule_id, Severity::Error);
+        let aggregator = ViolationAggregator::with_severities(counts, severities);
+
+        let violations = vec![
+            create_test_violation("no-unwrap", "src/a.rs", "src", 1),
+            create_test_violation("no-unwrap", "src/b.rs", "src", 2),
+        ];
+
+        let result = aggregator.aggregate(violations);
+
+        assert!(!result.passed);
+    }
+
+    #[test]
+    fn test_incremental_aggregator_warning_severity_does_not_fail_overall_result() {
+        let mut counts = CountsManager::new();
+        let rule_id = RuleId::new("no-unwrap").unwrap();
+        counts.set_count(&rule_id, &RegionPath::new("src"), 1);
+
+        let mut severities = HashMap::new();
+        severities.insert(rule_id, Severity::Warning);
+        let mut aggregator = IncrementalAggregator::with_severities(counts, severities);
+
+        let (changed, overall_passed) = aggregator.update_file(
+            PathBuf::from("src/a.rs"),
+            vec![
+                create_test_violation("no-unwrap", "src/a.rs", "src", 1),
+                create_test_violation("no-unwrap", "src/a.rs", "src", 2),
+            ],
+        );
+
+        assert!(!changed[0].passed);
+        assert!(overall_passed);
+    }
+
+    #[test]
+    fn test_combine_tags_each_status_with_its_config_path() {
+        let counts_a = CountsManager::new();
+        let result_a = ViolationAggregator::new(counts_a).aggregate(vec![create_test_violation(
+            "no-unwrap",
+            "a/main.rs",
+            "a",
+            1,
+        )]);
+
+        let counts_b = CountsManager::new();
+        let result_b = ViolationAggregator::new(counts_b).aggregate(vec![create_test_violation(
+            "no-todo",
+            "b/main.rs",
+            "b",
+            1,
+        )]);
+
+        let combined = AggregationResult::combine(vec![
+            ("ratchet-a.toml".to_string(), result_a),
+            ("ratchet-b.toml".to_string(), result_b),
+        ]);
+
+        assert_eq!(combined.statuses.len(), 2);
+        assert!(combined
+            .statuses
+            .iter()
+            .any(|s| s.source == "ratchet-a.toml"));
+        assert!(combined
+            .statuses
+            .iter()
+            .any(|s| s.source == "ratchet-b.toml"));
+    }
+
+    #[test]
+    fn test_combine_sums_totals_and_ands_passed() {
+        let mut counts_a = CountsManager::new();
+        counts_a.set_count(&RuleId::new("no-unwrap").unwrap(), &RegionPath::new("a"), 5);
+        let result_a = ViolationAggregator::new(counts_a).aggregate(vec![create_test_violation(
+            "no-unwrap",
+            "a/main.rs",
+            "a",
+            1,
+        )]);
+
+        let mut counts_b = CountsManager::new();
+        counts_b.set_count(&RuleId::new("no-todo").unwrap(), &RegionPath::new("b"), 0);
+        let result_b = ViolationAggregator::new(counts_b).aggregate(vec![create_test_violation(
+            "no-todo",
+            "b/main.rs",
+            "b",
+            1,
+        )]);
+
+        // result_a passes (1 <= 5), result_b fails (1 > 0).
+        assert!(result_a.passed);
+        assert!(!result_b.passed);
+
+        let combined = AggregationResult::combine(vec![
+            ("ratchet-a.toml".to_string(), result_a),
+            ("ratchet-b.toml".to_string(), result_b),
+        ]);
+
+        assert_eq!(combined.total_violations, 2);
+        // ANDed across shards: one of the two failed, so combined fails too.
+        assert!(!combined.passed);
+    }
+
+    #[test]
+    fn test_combine_of_empty_input_is_empty_and_passed() {
+        let combined = AggregationResult::combine(vec![]);
+
+        assert!(combined.statuses.is_empty());
+        // Vacuously true, matching `aggregate`'s `all_passed` and
+        // `jsonl::merge`'s `.all()` for the same no-shards case.
+        assert!(combined.passed);
+        assert_eq!(combined.total_violations, 0);
     }
 }
@@ -1,127 +1,706 @@
 #![forbid(unsafe_code)]
 
 //! Human-readable output formatter with colorization support
+//!
+//! [`render_coalesced_line`] builds its output through a [`FormatRecorder`]
+//! rather than coloring strings inline — see that type's doc comment for
+//! why. The rest of this file's rendering still calls [`colored`] directly;
+//! those spans are single, already-complete strings (a rule name, a
+//! location) that are never wrapped or split after coloring, so recording
+//! them wouldn't change behavior, only add indirection. Migrating them is
+//! left for whoever next touches this file's wrapping logic. Note that the
+//! long-snippet wrapping problem this recorder is partly motivated by was
+//! already fixed independently (snippets wrap at [`HumanFormatter::wrap_width`]
+//! display columns via [`wrap_graphemes`]); what `FormatRecorder` adds on
+//! top is making the *coloring* of a wrapped span provably safe rather than
+//! incidentally safe.
+
+use super::jsonl::Formatter;
+use crate::engine::aggregator::{AggregationResult, RuleRegionStatus, Severity};
+use crate::rules::Violation;
+use std::io::{self, IsTerminal, Write};
+use std::path::Path;
+use termcolor::{Ansi, Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Terminal width assumed when stdout isn't a TTY or width detection fails
+const DEFAULT_WRAP_WIDTH: usize = 80;
+
+/// Reads environment variables, injectable so tests can supply a fake map instead of the real process environment
+pub trait EnvVars {
+    /// Returns the variable's value, or `None` if it isn't set
+    fn get(&self, key: &str) -> Option<String>;
+}
+
+/// Reads variables from the real process environment via [`std::env::var`]
+pub struct ProcessEnvVars;
+
+impl EnvVars for ProcessEnvVars {
+    fn get(&self, key: &str) -> Option<String> {
+        std::env::var(key).ok()
+    }
+}
+
+/// Resolves the effective [`ColorChoice`] from the environment, honoring an explicit `requested` choice first
+///
+/// An explicit [`ColorChoice::Always`]/[`ColorChoice::Never`] passes through
+/// unchanged. Otherwise, following the informal `NO_COLOR`/`CLICOLOR`
+/// conventions: `NO_COLOR` (non-empty) forces color off, `CLICOLOR_FORCE`
+/// (non-empty) forces it on, and `CLICOLOR=0` forces it off, checked in that
+/// order; if none apply, `requested` (typically [`ColorChoice::Auto`]) is
+/// returned as-is and its own TTY detection still applies.
+fn resolve_color_choice(requested: ColorChoice, vars: &impl EnvVars) -> ColorChoice {
+    if matches!(requested, ColorChoice::Always | ColorChoice::Never) {
+        return requested;
+    }
+    if vars.get("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+        return ColorChoice::Never;
+    }
+    if vars.get("CLICOLOR_FORCE").is_some_and(|v| !v.is_empty()) {
+        return ColorChoice::Always;
+    }
+    if vars.get("CLICOLOR").as_deref() == Some("0") {
+        return ColorChoice::Never;
+    }
+    requested
+}
 
-use crate::engine::aggregator::{AggregationResult, RuleRegionStatus};
-use std::io::{self, Write};
-use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
+/// Left indent applied to every rendered snippet/underline line
+const SNIPPET_INDENT: usize = 6;
+
+/// One source line carrying one or more violations, grouped for single-line rendering
+///
+/// `spans` holds a `(column, end_column)` pair per violation on this line, in
+/// the order the violations were found; when more than one marker lands on
+/// the same line (e.g. a stray `TODO` next to a `FIXME`) the line is printed
+/// once with every span underlined, instead of once per violation.
+struct CoalescedLine<'a> {
+    file: &'a Path,
+    line: u32,
+    snippet: &'a str,
+    spans: Vec<(u32, u32)>,
+}
+
+impl CoalescedLine<'_> {
+    /// `file:line:column` when exactly one marker landed on this line, `file:line` otherwise
+    ///
+    /// Dropping the column once several markers share a line avoids implying
+    /// one of them is more relevant than the others; the underline under the
+    /// rendered snippet carries the precise per-marker positions instead.
+    fn location(&self) -> String {
+        match self.spans.as_slice() {
+            [(column, _)] => format!("{}:{}:{}", self.file.display(), self.line, column),
+            _ => format!("{}:{}", self.file.display(), self.line),
+        }
+    }
+}
+
+/// Groups `violations` by `(file, line)`, sorted so output is deterministic
+fn coalesce_by_line(violations: &[Violation]) -> Vec<CoalescedLine<'_>> {
+    let mut sorted: Vec<&Violation> = violations.iter().collect();
+    sorted.sort_by(|a, b| (a.file.as_path(), a.line).cmp(&(b.file.as_path(), b.line)));
+
+    let mut lines: Vec<CoalescedLine> = Vec::new();
+    for violation in sorted {
+        if let Some(last) = lines.last_mut() {
+            if last.file == violation.file.as_path() && last.line == violation.line {
+                last.spans.push((violation.column, violation.end_column));
+                continue;
+            }
+        }
+        lines.push(CoalescedLine {
+            file: violation.file.as_path(),
+            line: violation.line,
+            snippet: violation.snippet.as_str(),
+            spans: vec![(violation.column, violation.end_column)],
+        });
+    }
+    lines
+}
+
+/// Builds a `^^^^` underline marking every `(column, end_column)` span (1-indexed, into `text`) at once
+fn render_underline(text: &str, spans: &[(u32, u32)]) -> String {
+    let width = text.chars().count();
+    let mut marks = vec![' '; width];
+    for &(start, end) in spans {
+        let start = (start.saturating_sub(1) as usize).min(width);
+        let end = (end.saturating_sub(1) as usize).max(start + 1).min(width);
+        for mark in marks.iter_mut().take(end).skip(start) {
+            *mark = '^';
+        }
+    }
+    marks.into_iter().collect()
+}
+
+/// A semantic style a [`FormatRecorder`] span carries, resolved to a [`ColorSpec`] only at render time
+///
+/// Keeping the label separate from the color lets text and style get
+/// recorded as plain data while a snippet is still being wrapped, instead of
+/// embedding ANSI codes inline before wrapping happens — see
+/// [`FormatRecorder`]'s own doc comment for why that ordering matters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Label {
+    Underline,
+}
+
+impl Label {
+    /// The [`ColorSpec`] this label resolves to when a [`FormatRecorder`] is rendered with `colorize: true`
+    fn color_spec(self) -> ColorSpec {
+        let mut spec = ColorSpec::new();
+        match self {
+            Label::Underline => {
+                spec.set_fg(Some(Color::Red));
+            }
+        }
+        spec
+    }
+}
+
+/// Records formatted output as a sequence of `(text, label)` spans instead of writing ANSI codes inline
+///
+/// Borrows the approach jj's `formatter.rs` uses: push plain and labeled text
+/// as they're produced, and defer turning a label into ANSI bytes (or
+/// dropping it for a plain [`ColorChoice::Never`] render) until
+/// [`FormatRecorder::render`] walks the finished span list. That ordering is
+/// what [`render_coalesced_line`] needs — wrapping and indenting a snippet
+/// has to see the underline as plain `^`/` ` text to measure and split it
+/// correctly, which isn't possible once it's already interleaved with escape
+/// codes. Coloring a whole already-wrapped line the way the rest of this
+/// file's `colored()` call sites do happens not to split an escape sequence
+/// in practice, but only because none of those spans are wrapped after
+/// they're colored; `FormatRecorder` makes that ordering a guarantee instead
+/// of an accident, for the one call site where wrapping and coloring
+/// genuinely interact.
+struct FormatRecorder {
+    spans: Vec<(String, Option<Label>)>,
+}
+
+impl FormatRecorder {
+    fn new() -> Self {
+        FormatRecorder { spans: Vec::new() }
+    }
+
+    /// Appends `text` carrying no label (structural text: punctuation, indentation, newlines)
+    fn push_plain(&mut self, text: &str) {
+        self.spans.push((text.to_string(), None));
+    }
+
+    /// Appends `text` carrying `label`
+    fn push_labeled(&mut self, text: &str, label: Label) {
+        self.spans.push((text.to_string(), Some(label)));
+    }
+
+    /// Renders every recorded span in order, coloring labeled spans per [`Label::color_spec`] when `colorize` is true
+    fn render(&self, colorize: bool) -> String {
+        self.spans
+            .iter()
+            .map(|(text, label)| match label {
+                Some(label) => colored(text, &label.color_spec(), colorize),
+                None => text.clone(),
+            })
+            .collect()
+    }
+}
+
+/// Renders one [`CoalescedLine`] as the trimmed snippet followed by its underline, both indented to match
+///
+/// Wraps the snippet at `wrap_width` display columns (measured in grapheme
+/// cells, via [`wrap_graphemes`]), so a long or wide-character snippet
+/// doesn't overflow the terminal; continuation lines line up under the
+/// first one. Builds through a [`FormatRecorder`] so the underline is
+/// measured and wrapped as plain text before it's colored, rather than
+/// coloring it first and hoping wrapping never lands inside an escape code.
+fn render_coalesced_line(coalesced: &CoalescedLine, wrap_width: usize, colorize: bool) -> String {
+    let leading_ws = coalesced.snippet.len() - coalesced.snippet.trim_start().len();
+    let display = coalesced.snippet.trim();
+    let shift = leading_ws as u32;
+    let adjusted_spans: Vec<(u32, u32)> = coalesced
+        .spans
+        .iter()
+        .map(|&(start, end)| (start.saturating_sub(shift), end.saturating_sub(shift)))
+        .collect();
+
+    let budget = wrap_width.saturating_sub(SNIPPET_INDENT).max(1);
+    let mut recorder = FormatRecorder::new();
+    for (line, line_start) in wrap_graphemes(display, budget) {
+        let line_len = line.chars().count() as u32;
+        let line_lo = line_start + 1;
+        let line_hi = line_start + line_len + 1;
+        let local_spans: Vec<(u32, u32)> = adjusted_spans
+            .iter()
+            .filter(|&&(start, end)| start < line_hi && end > line_lo)
+            .map(|&(start, end)| {
+                (
+                    start.max(line_lo) - line_start,
+                    end.min(line_hi) - line_start,
+                )
+            })
+            .collect();
+        recorder.push_plain("      ");
+        recorder.push_plain(&line);
+        recorder.push_plain("\n      ");
+        recorder.push_labeled(&render_underline(&line, &local_spans), Label::Underline);
+        recorder.push_plain("\n");
+    }
+    recorder.render(colorize)
+}
+
+/// Splits `text` into lines no wider than `width` display columns
+///
+/// Measures each grapheme cluster's cell width with [`UnicodeWidthStr`] (so
+/// CJK characters count as 2 columns and combining marks as 0) rather than
+/// counting `char`s, and breaks between grapheme clusters rather than
+/// `char`s so combining marks stay attached to their base character. A
+/// single grapheme cluster wider than `width` is placed alone on its own
+/// line rather than split. Returns one `(line_text, char_offset)` pair per
+/// line, where `char_offset` is the 0-indexed character count of `text`
+/// preceding that line — used to re-home `char`-indexed spans onto whichever
+/// wrapped line they fall in.
+fn wrap_graphemes(text: &str, width: usize) -> Vec<(String, u32)> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    let mut current_width = 0usize;
+    let mut line_start = 0u32;
+    let mut chars_seen = 0u32;
+
+    for grapheme in text.graphemes(true) {
+        let grapheme_width = UnicodeWidthStr::width(grapheme).max(1);
+        if !current.is_empty() && current_width + grapheme_width > width {
+            lines.push((std::mem::take(&mut current), line_start));
+            current_width = 0;
+            line_start = chars_seen;
+        }
+        current.push_str(grapheme);
+        current_width += grapheme_width;
+        chars_seen += grapheme.chars().count() as u32;
+    }
+    lines.push((current, line_start));
+    lines
+}
 
 /// Human-readable output formatter
 ///
 /// Formats aggregation results for terminal display with optional colors.
 pub struct HumanFormatter {
     color_choice: ColorChoice,
+    /// Explicit snippet-wrap width override; `None` auto-detects the terminal width, see [`Self::wrap_width`]
+    wrap_width: Option<usize>,
+    verbosity: Verbosity,
+    /// When set, the summary notes a rule sitting exactly at its budget as having no remaining headroom
+    strict: bool,
+}
+
+/// How much per-violation detail [`HumanFormatter`] includes in its output
+///
+/// Following libtest's terse-formatter model — collapse per-item output
+/// into compact status lines, and only expand detail for failures — on a
+/// large result set the full per-violation dump becomes unusable
+/// scrollback, so [`Verbosity`] trades detail for compactness.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verbosity {
+    /// The `Summary:` block and final verdict only; no rule headers or violation listing
+    Quiet,
+    /// One `{symbol} {rule}  {actual}/{budget}` line per rule; violations are listed only for rules over budget
+    Terse,
+    /// Every rule's violations are listed in full. This formatter's original behavior, and the default
+    Normal,
+    /// Currently renders the same as [`Verbosity::Normal`]; reserved for output richer than today's full listing
+    Verbose,
+}
+
+impl Default for Verbosity {
+    fn default() -> Self {
+        Verbosity::Normal
+    }
 }
 
 impl HumanFormatter {
     /// Creates a new HumanFormatter with the specified color choice
+    ///
+    /// Snippet wrapping auto-detects the terminal width on each call,
+    /// falling back to [`DEFAULT_WRAP_WIDTH`] when stdout isn't a TTY or
+    /// detection fails. Use [`Self::with_wrap_width`] to pin an explicit
+    /// width instead, e.g. for non-interactive callers that still want
+    /// deterministic wrapping.
     pub fn new(color_choice: ColorChoice) -> Self {
-        HumanFormatter { color_choice }
+        HumanFormatter {
+            color_choice,
+            wrap_width: None,
+            verbosity: Verbosity::default(),
+            strict: false,
+        }
+    }
+
+    /// Creates a new HumanFormatter, resolving `color_choice` and strict mode from the environment
+    ///
+    /// `requested` is used as-is when it's [`ColorChoice::Always`] or
+    /// [`ColorChoice::Never`]; otherwise see [`resolve_color_choice`] for how
+    /// `NO_COLOR`/`CLICOLOR`/`CLICOLOR_FORCE` can override it. `RATCHET_STRICT`
+    /// (any non-empty value) enables [`Self::with_strict`]. `vars` is read
+    /// rather than the process environment directly so tests can supply a
+    /// fake map; callers wanting the real environment pass [`ProcessEnvVars`].
+    pub fn from_env(requested: ColorChoice, vars: &impl EnvVars) -> Self {
+        let strict = vars.get("RATCHET_STRICT").is_some_and(|v| !v.is_empty());
+        Self::new(resolve_color_choice(requested, vars)).with_strict(strict)
+    }
+
+    /// Overrides the auto-detected terminal width with an explicit wrap width
+    ///
+    /// Intended for non-interactive callers (CI logs, snapshot tests) that
+    /// want wrapping behavior independent of whatever terminal happens to be
+    /// attached to stdout.
+    pub fn with_wrap_width(mut self, width: usize) -> Self {
+        self.wrap_width = Some(width);
+        self
+    }
+
+    /// Overrides the default [`Verbosity::Normal`] with `verbosity`
+    pub fn with_verbosity(mut self, verbosity: Verbosity) -> Self {
+        self.verbosity = verbosity;
+        self
+    }
+
+    /// Enables strict mode: the summary flags a rule sitting exactly at its budget as having no remaining headroom
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// The display-column width snippets wrap at: the explicit override if set, else the detected terminal width, else [`DEFAULT_WRAP_WIDTH`]
+    fn wrap_width(&self) -> usize {
+        self.wrap_width.unwrap_or_else(|| {
+            term_size::dimensions_stdout()
+                .map(|(width, _)| width)
+                .unwrap_or(DEFAULT_WRAP_WIDTH)
+        })
+    }
+
+    /// Whether this formatter's output should carry ANSI escape codes
+    ///
+    /// Resolves `color_choice` against the real process environment via
+    /// [`resolve_color_choice`] (so `NO_COLOR`/`CLICOLOR_FORCE`/`CLICOLOR=0`
+    /// apply the same way they do for [`Self::from_env`]), then settles any
+    /// remaining [`ColorChoice::Auto`] by checking whether stdout is a
+    /// terminal.
+    fn use_color(&self) -> bool {
+        match resolve_color_choice(self.color_choice, &ProcessEnvVars) {
+            ColorChoice::Never => false,
+            ColorChoice::Always | ColorChoice::AlwaysAnsi => true,
+            ColorChoice::Auto => io::stdout().is_terminal(),
+        }
     }
 
     /// Format the aggregation result for human consumption
     ///
-    /// Returns a formatted string suitable for terminal display.
+    /// Returns a formatted string suitable for terminal display, rendered
+    /// at this formatter's configured [`Verbosity`] (full per-violation
+    /// detail by default). Carries ANSI color codes when [`Self::use_color`]
+    /// resolves to `true` for this formatter's `color_choice`.
     pub fn format(&self, result: &AggregationResult) -> String {
-        let mut output = String::new();
+        render_for_verbosity(
+            result,
+            self.verbosity,
+            self.wrap_width(),
+            self.strict,
+            self.use_color(),
+        )
+    }
+}
 
-        // Group statuses by rule_id
-        let mut current_rule: Option<&str> = None;
+impl Default for HumanFormatter {
+    fn default() -> Self {
+        Self::new(ColorChoice::Auto)
+    }
+}
 
-        for status in &result.statuses {
-            // If this is a new rule, print the rule header
-            if current_rule != Some(status.rule_id.as_str()) {
-                if current_rule.is_some() {
-                    output.push('\n');
-                }
+impl Formatter for HumanFormatter {
+    /// Formats `result`, omitting the per-violation listing when `verbose` is false
+    ///
+    /// The `Summary:` block and final `Check PASSED`/`FAILED` line are
+    /// always included; `verbose` only controls whether each rule's
+    /// individual violations are listed underneath its header.
+    fn format(&self, result: &AggregationResult, verbose: bool) -> String {
+        render(
+            result,
+            verbose,
+            self.wrap_width(),
+            self.strict,
+            self.use_color(),
+        )
+    }
+}
 
-                // Count violations for this rule across all regions
-                let rule_violations: Vec<&RuleRegionStatus> = result
-                    .statuses
-                    .iter()
-                    .filter(|s| s.rule_id == status.rule_id)
-                    .collect();
-                let total_violations: u64 = rule_violations.iter().map(|s| s.actual_count).sum();
+/// Renders a [`Severity`] the way it's spelled in the rule header, e.g. `no-unwrap (error) [...]`
+fn severity_label(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "info",
+    }
+}
 
-                // Rule header: no-unwrap (error) [2 violations]
-                output.push_str(&format!(
-                    "{} [{}]\n\n",
-                    status.rule_id.as_str(),
-                    if total_violations == 1 {
-                        "1 violation".to_string()
-                    } else {
-                        format!("{} violations", total_violations)
-                    }
-                ));
+/// The terminal color a rule header's severity token is rendered in
+fn severity_color(severity: Severity) -> Color {
+    match severity {
+        Severity::Error => Color::Red,
+        Severity::Warning => Color::Yellow,
+        Severity::Info => Color::Blue,
+    }
+}
 
-                current_rule = Some(status.rule_id.as_str());
-            }
+/// Wraps `text` in the ANSI escape codes for `spec` when `colorize` is true, else returns it unchanged
+///
+/// Goes through [`termcolor::Ansi`] rather than hand-writing escape
+/// sequences, so the codes [`HumanFormatter::format`] embeds in its returned
+/// `String` stay in lockstep with the ones [`HumanFormatter::write_to_stdout`]
+/// asks `termcolor` to emit to a real terminal.
+fn colored(text: &str, spec: &ColorSpec, colorize: bool) -> String {
+    if !colorize || text.is_empty() {
+        return text.to_string();
+    }
+    let mut buf = Ansi::new(Vec::new());
+    buf.set_color(spec)
+        .expect("writing ANSI codes to an in-memory buffer never fails");
+    write!(buf, "{}", text).expect("writing ANSI codes to an in-memory buffer never fails");
+    buf.reset()
+        .expect("writing ANSI codes to an in-memory buffer never fails");
+    String::from_utf8(buf.into_inner())
+        .expect("Ansi only writes the given text plus ASCII escape codes")
+}
 
-            // Print violations for this region (only if there are violations)
-            if !status.violations.is_empty() {
-                for violation in &status.violations {
-                    output.push_str(&format!(
-                        "  {}:{}:{}\n",
-                        violation.file.display(),
-                        violation.line,
-                        violation.column
-                    ));
-                    output.push_str(&format!("      {}\n", violation.snippet.trim()));
-                    output.push('\n');
-                }
+/// Shared body for [`HumanFormatter::format`] and its [`Formatter`] impl
+fn render(
+    result: &AggregationResult,
+    verbose: bool,
+    wrap_width: usize,
+    strict: bool,
+    colorize: bool,
+) -> String {
+    let mut output = String::new();
+
+    // Group statuses by rule_id
+    let mut current_rule: Option<&str> = None;
+
+    for status in &result.statuses {
+        // If this is a new rule, print the rule header
+        if current_rule != Some(status.rule_id.as_str()) {
+            if current_rule.is_some() {
+                output.push('\n');
             }
-        }
 
-        // Summary section
-        if !result.statuses.is_empty() {
-            output.push_str("Summary:\n\n");
+            // Count violations for this rule across all regions
+            let rule_violations: Vec<&RuleRegionStatus> = result
+                .statuses
+                .iter()
+                .filter(|s| s.rule_id == status.rule_id)
+                .collect();
+            let total_violations: u64 = rule_violations.iter().map(|s| s.actual_count).sum();
+            let violation_count_text = if total_violations == 1 {
+                "1 violation".to_string()
+            } else {
+                format!("{} violations", total_violations)
+            };
 
-            for status in &result.statuses {
-                let symbol = if status.passed { "✓" } else { "✗" };
-                let status_text = if status.passed {
-                    format!(
-                        "{} violations (budget: {})",
-                        status.actual_count, status.budget
-                    )
-                } else {
-                    let exceeded = status.actual_count - status.budget;
-                    format!(
-                        "{} violations (budget: {}) exceeded by {}",
-                        status.actual_count, status.budget, exceeded
-                    )
-                };
+            // Rule header: no-unwrap (error) [2 violations]
+            output.push_str(&format!(
+                "{} ({}) [{}]\n\n",
+                colored(
+                    status.rule_id.as_str(),
+                    ColorSpec::new().set_bold(true),
+                    colorize
+                ),
+                colored(
+                    severity_label(status.severity),
+                    ColorSpec::new().set_fg(Some(severity_color(status.severity))),
+                    colorize
+                ),
+                colored(
+                    &violation_count_text,
+                    ColorSpec::new().set_bold(true),
+                    colorize
+                )
+            ));
+
+            current_rule = Some(status.rule_id.as_str());
+        }
 
+        // Print violations for this region (only if there are violations)
+        if verbose && !status.violations.is_empty() {
+            for coalesced in coalesce_by_line(&status.violations) {
                 output.push_str(&format!(
-                    "  {} {}: {}\n",
-                    symbol,
-                    status.rule_id.as_str(),
-                    status_text
+                    "  {}\n",
+                    colored(
+                        &coalesced.location(),
+                        ColorSpec::new().set_fg(Some(Color::Cyan)),
+                        colorize
+                    )
                 ));
+                output.push_str(&render_coalesced_line(&coalesced, wrap_width, colorize));
+                output.push('\n');
             }
+        }
+    }
 
-            output.push('\n');
+    output.push_str(&render_summary_block(result, strict, colorize));
+    output
+}
 
-            // Final check status
-            if result.passed {
-                output.push_str("Check PASSED\n");
+/// Renders the `Summary:` block and final `Check PASSED`/`FAILED` line, shared by every [`Verbosity`]
+///
+/// When `strict` is set, a rule sitting exactly at its budget (`passed` but
+/// with no room left before the next violation would exceed it) gets a
+/// trailing "no remaining headroom" note, giving early warning before the
+/// next ratchet-down turns it into a failure.
+fn render_summary_block(result: &AggregationResult, strict: bool, colorize: bool) -> String {
+    let mut output = String::new();
+
+    if !result.statuses.is_empty() {
+        output.push_str("Summary:\n\n");
+
+        for status in &result.statuses {
+            let symbol = if status.passed {
+                colored("✓", ColorSpec::new().set_fg(Some(Color::Green)), colorize)
             } else {
-                let rules_exceeded = result.statuses.iter().filter(|s| !s.passed).count();
-                output.push_str(&format!(
-                    "Check FAILED: {} rule{} exceeded budget\n",
-                    rules_exceeded,
-                    if rules_exceeded == 1 { "" } else { "s" }
-                ));
-            }
+                colored("✗", ColorSpec::new().set_fg(Some(Color::Red)), colorize)
+            };
+            let status_text = if status.passed {
+                format!(
+                    "{} violations (budget: {})",
+                    status.actual_count, status.budget
+                )
+            } else {
+                let exceeded = status.actual_count - status.budget;
+                format!(
+                    "{} violations (budget: {}) exceeded by {}",
+                    status.actual_count, status.budget, exceeded
+                )
+            };
+            let status_text = if strict && status.passed && status.actual_count == status.budget {
+                format!("{} (no remaining headroom)", status_text)
+            } else {
+                status_text
+            };
+
+            output.push_str(&format!(
+                "  {} {}: {}\n",
+                symbol,
+                status.rule_id.as_str(),
+                status_text
+            ));
+        }
+
+        output.push('\n');
+
+        // Final check status
+        if result.passed {
+            output.push_str("Check PASSED\n");
         } else {
-            output.push_str("No violations found\n");
+            let rules_exceeded = result.statuses.iter().filter(|s| !s.passed).count();
+            output.push_str(&format!(
+                "Check FAILED: {} rule{} exceeded budget\n",
+                rules_exceeded,
+                if rules_exceeded == 1 { "" } else { "s" }
+            ));
+        }
+    } else {
+        output.push_str(&colored(
+            "No violations found",
+            ColorSpec::new().set_fg(Some(Color::Green)),
+            colorize,
+        ));
+        output.push('\n');
+    }
+
+    output
+}
+
+/// One line per rule (`✗ no-todo  4/3`), expanding the violation listing only for rules over budget
+fn render_terse(
+    result: &AggregationResult,
+    wrap_width: usize,
+    strict: bool,
+    colorize: bool,
+) -> String {
+    let mut output = String::new();
+    let mut seen_rules: Vec<&str> = Vec::new();
+
+    for status in &result.statuses {
+        if seen_rules.contains(&status.rule_id.as_str()) {
+            continue;
+        }
+        seen_rules.push(status.rule_id.as_str());
+
+        let rule_statuses: Vec<&RuleRegionStatus> = result
+            .statuses
+            .iter()
+            .filter(|s| s.rule_id == status.rule_id)
+            .collect();
+        let total_actual: u64 = rule_statuses.iter().map(|s| s.actual_count).sum();
+        let total_budget: u64 = rule_statuses.iter().map(|s| s.budget).sum();
+        let rule_failed = rule_statuses.iter().any(|s| !s.passed);
+
+        let symbol = if rule_failed {
+            colored("✗", ColorSpec::new().set_fg(Some(Color::Red)), colorize)
+        } else {
+            colored("✓", ColorSpec::new().set_fg(Some(Color::Green)), colorize)
+        };
+        output.push_str(&format!(
+            "{} {}  {}/{}\n",
+            symbol,
+            status.rule_id.as_str(),
+            total_actual,
+            total_budget
+        ));
+
+        if rule_failed {
+            for failing in rule_statuses.iter().filter(|s| !s.passed) {
+                for coalesced in coalesce_by_line(&failing.violations) {
+                    output.push_str(&format!(
+                        "  {}\n",
+                        colored(
+                            &coalesced.location(),
+                            ColorSpec::new().set_fg(Some(Color::Cyan)),
+                            colorize
+                        )
+                    ));
+                    output.push_str(&render_coalesced_line(&coalesced, wrap_width, colorize));
+                    output.push('\n');
+                }
+            }
         }
+    }
+
+    output.push('\n');
+    output.push_str(&render_summary_block(result, strict, colorize));
+    output
+}
 
-        output
+/// Renders `result` at the given [`Verbosity`]
+fn render_for_verbosity(
+    result: &AggregationResult,
+    verbosity: Verbosity,
+    wrap_width: usize,
+    strict: bool,
+    colorize: bool,
+) -> String {
+    match verbosity {
+        Verbosity::Quiet => render_summary_block(result, strict, colorize),
+        Verbosity::Terse => render_terse(result, wrap_width, strict, colorize),
+        Verbosity::Normal | Verbosity::Verbose => {
+            render(result, true, wrap_width, strict, colorize)
+        }
     }
+}
 
+impl HumanFormatter {
     /// Write the formatted output to stdout with colors
     ///
     /// This method handles colorization and writes directly to stdout.
+    /// Honors [`Verbosity::Quiet`] by omitting rule headers and violation
+    /// listings; [`Verbosity::Terse`]'s one-line-per-rule layout is only
+    /// implemented for [`Self::format`] today, so this falls back to the
+    /// full listing for it.
     pub fn write_to_stdout(&self, result: &AggregationResult) -> io::Result<()> {
         let mut stdout = StandardStream::stdout(self.color_choice);
 
@@ -129,8 +708,8 @@ impl HumanFormatter {
         let mut current_rule: Option<&str> = None;
 
         for status in &result.statuses {
-            // If this is a new rule, print the rule header
-            if current_rule != Some(status.rule_id.as_str()) {
+            // If this is a new rule, print the rule header (skipped entirely in `Verbosity::Quiet`)
+            if self.verbosity != Verbosity::Quiet && current_rule != Some(status.rule_id.as_str()) {
                 if current_rule.is_some() {
                     writeln!(stdout)?;
                 }
@@ -148,10 +727,11 @@ impl HumanFormatter {
                 write!(stdout, "{}", status.rule_id.as_str())?;
                 stdout.reset()?;
 
-                write!(stdout, " ")?;
-
-                // Note: Severity information could be displayed here if available
-                // Currently, Violation doesn't have a severity field
+                write!(stdout, " (")?;
+                stdout.set_color(ColorSpec::new().set_fg(Some(severity_color(status.severity))))?;
+                write!(stdout, "{}", severity_label(status.severity))?;
+                stdout.reset()?;
+                write!(stdout, ") ")?;
 
                 stdout.set_color(ColorSpec::new().set_bold(true))?;
                 write!(
@@ -171,20 +751,18 @@ impl HumanFormatter {
             }
 
             // Print violations for this region
-            if !status.violations.is_empty() {
-                for violation in &status.violations {
+            if self.verbosity != Verbosity::Quiet && !status.violations.is_empty() {
+                for coalesced in coalesce_by_line(&status.violations) {
                     write!(stdout, "  ")?;
                     stdout.set_color(ColorSpec::new().set_fg(Some(Color::Cyan)))?;
+                    write!(stdout, "{}", coalesced.location())?;
+                    stdout.reset()?;
+                    writeln!(stdout)?;
                     write!(
                         stdout,
-                        "{}:{}:{}",
-                        violation.file.display(),
-                        violation.line,
-                        violation.column
+                        "{}",
+                        render_coalesced_line(&coalesced, self.wrap_width(), false)
                     )?;
-                    stdout.reset()?;
-                    writeln!(stdout)?;
-                    writeln!(stdout, "      {}", violation.snippet.trim())?;
                     writeln!(stdout)?;
                 }
             }
@@ -219,7 +797,13 @@ impl HumanFormatter {
                     stdout.set_color(ColorSpec::new().set_bold(true))?;
                     write!(stdout, "{}", status.budget)?;
                     stdout.reset()?;
-                    writeln!(stdout, ")")?;
+                    write!(stdout, ")")?;
+                    if self.strict && status.actual_count == status.budget {
+                        stdout.set_color(ColorSpec::new().set_fg(Some(Color::Yellow)))?;
+                        write!(stdout, " (no remaining headroom)")?;
+                        stdout.reset()?;
+                    }
+                    writeln!(stdout)?;
                 } else {
                     let exceeded = status.actual_count - status.budget;
                     stdout.set_color(ColorSpec::new().set_bold(true))?;
@@ -302,13 +886,19 @@ mod tests {
         budget: u64,
         violations: Vec<Violation>,
     ) -> RuleRegionStatus {
+        let passed = actual_count <= budget;
         RuleRegionStatus {
             rule_id: RuleId::new(rule_id).unwrap(),
             region: RegionPath::new(region),
             actual_count,
+            raw_count: actual_count,
             budget,
-            passed: actual_count <= budget,
+            passed,
+            over_budget: actual_count.saturating_sub(budget),
+            severity: Severity::Error,
+            top_offenders: Vec::new(),
             violations,
+            source: String::new(),
         }
     }
 
@@ -688,9 +1278,9 @@ mod tests {
         };
 
         let output = formatter.format(&result);
-        // format() always returns plain text without ANSI codes regardless of color_choice
-        // This is just testing that it doesn't error
+        // ColorChoice::Never always yields plain text, no ANSI codes
         assert!(output.contains("no-unwrap"));
+        assert!(!output.contains('\x1b'));
     }
 
     #[test]
@@ -713,14 +1303,14 @@ mod tests {
         };
 
         let output = formatter.format(&result);
-        // format() always returns plain text without ANSI codes regardless of color_choice
-        // This is just testing that it doesn't error
+        // ColorChoice::Always forces ANSI codes on regardless of whether stdout is a terminal
         assert!(output.contains("no-unwrap"));
+        assert!(output.contains('\x1b'));
     }
 
     #[test]
     fn test_format_color_choice_auto() {
-        // Test with ColorChoice::Auto
+        // ColorChoice::Auto with no attached terminal (the test harness's stdout) yields plain text
         let formatter = HumanFormatter::new(ColorChoice::Auto);
         let violations = vec![create_test_violation(
             "no-unwrap",
@@ -738,9 +1328,8 @@ mod tests {
         };
 
         let output = formatter.format(&result);
-        // format() always returns plain text without ANSI codes regardless of color_choice
-        // This is just testing that it doesn't error
         assert!(output.contains("no-unwrap"));
+        assert!(!output.contains('\x1b'));
     }
 
     #[test]
@@ -821,4 +1410,505 @@ mod tests {
         assert!(output.contains("✓"));
         assert!(output.contains("Check PASSED"));
     }
+
+    fn create_test_violation_at(
+        rule_id: &str,
+        file_path: &str,
+        line: u32,
+        column: u32,
+        end_column: u32,
+        snippet: &str,
+    ) -> Violation {
+        Violation {
+            rule_id: RuleId::new(rule_id).unwrap(),
+            file: PathBuf::from(file_path),
+            line,
+            column,
+            end_line: line,
+            end_column,
+            snippet: snippet.to_string(),
+            message: "Test violation".to_string(),
+            region: RegionPath::new("src"),
+        }
+    }
+
+    #[test]
+    fn test_render_underline_marks_a_single_span() {
+        assert_eq!(render_underline("// TODO fix", &[(4, 8)]), "   ^^^^    ");
+    }
+
+    #[test]
+    fn test_render_underline_marks_several_spans_at_once() {
+        let underline = render_underline("// TODO .. FIXME", &[(4, 8), (12, 17)]);
+        assert_eq!(underline, "   ^^^^    ^^^^^");
+    }
+
+    #[test]
+    fn test_format_recorder_renders_plain_spans_unchanged_regardless_of_colorize() {
+        let mut recorder = FormatRecorder::new();
+        recorder.push_plain("no-unwrap ");
+        recorder.push_plain("violation");
+
+        assert_eq!(recorder.render(true), "no-unwrap violation");
+        assert_eq!(recorder.render(false), "no-unwrap violation");
+    }
+
+    #[test]
+    fn test_format_recorder_colors_labeled_spans_only_when_colorize_is_true() {
+        let mut recorder = FormatRecorder::new();
+        recorder.push_plain("   ");
+        recorder.push_labeled("^^^^", Label::Underline);
+
+        assert_eq!(recorder.render(false), "   ^^^^");
+        let colored_output = recorder.render(true);
+        assert_ne!(colored_output, "   ^^^^");
+        assert!(colored_output.contains("^^^^"));
+        assert!(colored_output.contains('\x1b'));
+    }
+
+    #[test]
+    fn test_coalesce_by_line_groups_same_file_and_line() {
+        let violations = vec![
+            create_test_violation_at("no-todo", "src/lib.rs", 5, 4, 8, "// TODO .. FIXME"),
+            create_test_violation_at("no-fixme", "src/lib.rs", 5, 12, 17, "// TODO .. FIXME"),
+        ];
+        let coalesced = coalesce_by_line(&violations);
+        assert_eq!(coalesced.len(), 1);
+        assert_eq!(coalesced[0].spans, vec![(4, 8), (12, 17)]);
+    }
+
+    #[test]
+    fn test_coalesce_by_line_keeps_different_lines_separate() {
+        let violations = vec![
+            create_test_violation_at("no-todo", "src/lib.rs", 5, 4, 8, "// TODO"),
+            create_test_violation_at("no-todo", "src/lib.rs", 6, 4, 8, "// TODO"),
+        ];
+        assert_eq!(coalesce_by_line(&violations).len(), 2);
+    }
+
+    #[test]
+    fn test_format_coalesces_multiple_markers_on_one_line() {
+        let formatter = HumanFormatter::new(ColorChoice::Never);
+        let violations = vec![
+            create_test_violation_at("no-todo", "src/lib.rs", 5, 4, 8, "// TODO .. FIXME"),
+            create_test_violation_at("no-todo", "src/lib.rs", 5, 12, 17, "// TODO .. FIXME"),
+        ];
+        let status = create_test_status("no-todo", "src", 2, 5, violations);
+        let result = AggregationResult {
+            statuses: vec![status],
+            passed: true,
+            total_violations: 2,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result);
+
+        // One line for both markers, no single column in the header, and
+        // both spans underlined.
+        assert!(output.contains("src/lib.rs:5\n"));
+        assert!(!output.contains("src/lib.rs:5:"));
+        assert!(output.contains("// TODO .. FIXME"));
+        assert!(output.contains("^^^^    ^^^^^"));
+    }
+
+    #[test]
+    fn test_format_single_marker_line_still_shows_column() {
+        let formatter = HumanFormatter::new(ColorChoice::Never);
+        let violations = vec![create_test_violation_at(
+            "no-todo",
+            "src/lib.rs",
+            5,
+            4,
+            8,
+            "// TODO fix",
+        )];
+        let status = create_test_status("no-todo", "src", 1, 5, violations);
+        let result = AggregationResult {
+            statuses: vec![status],
+            passed: true,
+            total_violations: 1,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result);
+        assert!(output.contains("src/lib.rs:5:4"));
+    }
+
+    #[test]
+    fn test_formatter_trait_verbose_includes_violation_listing() {
+        let formatter = HumanFormatter::new(ColorChoice::Never);
+        let violations = vec![create_test_violation(
+            "no-unwrap",
+            "src/main.rs",
+            "src",
+            10,
+            ".unwrap()",
+        )];
+        let status = create_test_status("no-unwrap", "src", 1, 5, violations);
+        let result = AggregationResult {
+            statuses: vec![status],
+            passed: true,
+            total_violations: 1,
+            violations_over_budget: 0,
+        };
+
+        let output = Formatter::format(&formatter, &result, true);
+        assert!(output.contains("src/main.rs:10"));
+        assert!(output.contains("Summary:"));
+    }
+
+    #[test]
+    fn test_formatter_trait_non_verbose_omits_violation_listing() {
+        let formatter = HumanFormatter::new(ColorChoice::Never);
+        let violations = vec![create_test_violation(
+            "no-unwrap",
+            "src/main.rs",
+            "src",
+            10,
+            ".unwrap()",
+        )];
+        let status = create_test_status("no-unwrap", "src", 1, 5, violations);
+        let result = AggregationResult {
+            statuses: vec![status],
+            passed: true,
+            total_violations: 1,
+            violations_over_budget: 0,
+        };
+
+        let output = Formatter::format(&formatter, &result, false);
+        assert!(!output.contains("src/main.rs:10"));
+        assert!(output.contains("Summary:"));
+        assert!(output.contains("Check PASSED"));
+    }
+
+    #[test]
+    fn test_wrap_graphemes_splits_at_display_width_not_char_count() {
+        // 9 single-column graphemes at width 4: "aaaa", " bbb", "b"
+        let lines = wrap_graphemes("aaaa bbbb", 4);
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].0, "aaaa");
+        assert_eq!(lines[1].0, " bbb");
+    }
+
+    #[test]
+    fn test_wrap_graphemes_counts_wide_characters_as_two_columns() {
+        // Each of these three characters is 2 display columns wide, so a
+        // budget of 4 should fit exactly two of them per line.
+        let lines = wrap_graphemes("日本語", 4);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].0, "日本");
+        assert_eq!(lines[1].0, "語");
+    }
+
+    #[test]
+    fn test_wrap_graphemes_never_splits_a_single_wide_grapheme() {
+        let lines = wrap_graphemes("🦀", 1);
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].0, "🦀");
+    }
+
+    #[test]
+    fn test_format_wraps_long_snippet_at_explicit_width() {
+        let formatter = HumanFormatter::new(ColorChoice::Never).with_wrap_width(20);
+        let violations = vec![create_test_violation(
+            "no-unwrap",
+            "src/main.rs",
+            "src",
+            10,
+            "some_very_long_receiver_expression.unwrap()",
+        )];
+        let status = create_test_status("no-unwrap", "src", 1, 5, violations);
+        let result = AggregationResult {
+            statuses: vec![status],
+            passed: true,
+            total_violations: 1,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result);
+        let full_snippet = "some_very_long_receiver_expression.unwrap()";
+        assert!(
+            !output.lines().any(|line| line.contains(full_snippet)),
+            "expected the snippet to wrap onto multiple lines: {output:?}"
+        );
+        assert!(output.contains("some_very_long"));
+    }
+
+    #[test]
+    fn test_format_keeps_short_snippet_on_one_line_regardless_of_width() {
+        let formatter = HumanFormatter::new(ColorChoice::Never).with_wrap_width(80);
+        let violations = vec![create_test_violation(
+            "no-unwrap",
+            "src/main.rs",
+            "src",
+            10,
+            ".unwrap()",
+        )];
+        let status = create_test_status("no-unwrap", "src", 1, 5, violations);
+        let result = AggregationResult {
+            statuses: vec![status],
+            passed: true,
+            total_violations: 1,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result);
+        assert!(output.contains("      .unwrap()"));
+    }
+
+    #[test]
+    fn test_format_renders_rule_severity_in_header() {
+        let formatter = HumanFormatter::new(ColorChoice::Never);
+        let mut status = create_test_status("no-unwrap", "src", 1, 5, vec![]);
+        status.severity = Severity::Warning;
+        let result = AggregationResult {
+            statuses: vec![status],
+            passed: true,
+            total_violations: 1,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result);
+        assert!(output.contains("no-unwrap (warning) ["));
+    }
+
+    #[test]
+    fn test_warning_severity_over_budget_does_not_flip_check_to_failed() {
+        let formatter = HumanFormatter::new(ColorChoice::Never);
+        let mut status = create_test_status("no-console-log", "src", 10, 5, vec![]);
+        status.severity = Severity::Warning;
+        status.passed = false;
+        let result = AggregationResult {
+            statuses: vec![status],
+            passed: true,
+            total_violations: 10,
+            violations_over_budget: 5,
+        };
+
+        let output = formatter.format(&result);
+        assert!(output.contains("Check PASSED"));
+        assert!(!output.contains("Check FAILED"));
+    }
+
+    #[test]
+    fn test_quiet_verbosity_omits_rule_headers_and_violations() {
+        let formatter = HumanFormatter::new(ColorChoice::Never).with_verbosity(Verbosity::Quiet);
+        let violations = vec![create_test_violation(
+            "no-unwrap",
+            "src/main.rs",
+            "src",
+            10,
+            ".unwrap()",
+        )];
+        let status = create_test_status("no-unwrap", "src", 1, 5, violations);
+        let result = AggregationResult {
+            statuses: vec![status],
+            passed: true,
+            total_violations: 1,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result);
+        assert!(!output.contains("no-unwrap ("));
+        assert!(!output.contains(".unwrap()"));
+        assert!(output.contains("Summary:"));
+        assert!(output.contains("Check PASSED"));
+    }
+
+    #[test]
+    fn test_terse_verbosity_renders_one_line_per_rule() {
+        let formatter = HumanFormatter::new(ColorChoice::Never).with_verbosity(Verbosity::Terse);
+        let status = create_test_status("no-unwrap", "src", 1, 5, vec![]);
+        let result = AggregationResult {
+            statuses: vec![status],
+            passed: true,
+            total_violations: 1,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result);
+        assert!(output.contains("✓ no-unwrap  1/5"));
+        assert!(output.contains("Check PASSED"));
+    }
+
+    #[test]
+    fn test_terse_verbosity_expands_violations_only_for_failing_rules() {
+        let formatter = HumanFormatter::new(ColorChoice::Never).with_verbosity(Verbosity::Terse);
+        let passing_violations = vec![create_test_violation(
+            "no-console-log",
+            "src/a.rs",
+            "src",
+            1,
+            "console.log()",
+        )];
+        let failing_violations = vec![create_test_violation(
+            "no-unwrap",
+            "src/b.rs",
+            "src",
+            20,
+            ".unwrap()",
+        )];
+        let passing = create_test_status("no-console-log", "src", 1, 5, passing_violations);
+        let mut failing = create_test_status("no-unwrap", "src", 10, 5, failing_violations);
+        failing.passed = false;
+        let result = AggregationResult {
+            statuses: vec![passing, failing],
+            passed: false,
+            total_violations: 11,
+            violations_over_budget: 5,
+        };
+
+        let output = formatter.format(&result);
+        assert!(output.contains("✓ no-console-log  1/5"));
+        assert!(output.contains("✗ no-unwrap  10/5"));
+        assert!(!output.contains("console.log()"));
+        assert!(output.contains(".unwrap()"));
+    }
+
+    struct FakeEnvVars(std::collections::HashMap<&'static str, &'static str>);
+
+    impl EnvVars for FakeEnvVars {
+        fn get(&self, key: &str) -> Option<String> {
+            self.0.get(key).map(|v| v.to_string())
+        }
+    }
+
+    #[test]
+    fn test_resolve_color_choice_no_color_forces_off() {
+        let vars = FakeEnvVars(std::collections::HashMap::from([("NO_COLOR", "1")]));
+        assert_eq!(
+            resolve_color_choice(ColorChoice::Auto, &vars),
+            ColorChoice::Never
+        );
+    }
+
+    #[test]
+    fn test_resolve_color_choice_empty_no_color_is_ignored() {
+        let vars = FakeEnvVars(std::collections::HashMap::from([("NO_COLOR", "")]));
+        assert_eq!(
+            resolve_color_choice(ColorChoice::Auto, &vars),
+            ColorChoice::Auto
+        );
+    }
+
+    #[test]
+    fn test_resolve_color_choice_clicolor_force_forces_on() {
+        let vars = FakeEnvVars(std::collections::HashMap::from([("CLICOLOR_FORCE", "1")]));
+        assert_eq!(
+            resolve_color_choice(ColorChoice::Auto, &vars),
+            ColorChoice::Always
+        );
+    }
+
+    #[test]
+    fn test_resolve_color_choice_clicolor_zero_forces_off() {
+        let vars = FakeEnvVars(std::collections::HashMap::from([("CLICOLOR", "0")]));
+        assert_eq!(
+            resolve_color_choice(ColorChoice::Auto, &vars),
+            ColorChoice::Never
+        );
+    }
+
+    #[test]
+    fn test_resolve_color_choice_explicit_choice_overrides_env() {
+        let vars = FakeEnvVars(std::collections::HashMap::from([("NO_COLOR", "1")]));
+        assert_eq!(
+            resolve_color_choice(ColorChoice::Always, &vars),
+            ColorChoice::Always
+        );
+    }
+
+    #[test]
+    fn test_from_env_enables_strict_from_ratchet_strict() {
+        let vars = FakeEnvVars(std::collections::HashMap::from([("RATCHET_STRICT", "1")]));
+        let formatter = HumanFormatter::from_env(ColorChoice::Never, &vars);
+
+        let status = create_test_status("no-unwrap", "src", 5, 5, vec![]);
+        let result = AggregationResult {
+            statuses: vec![status],
+            passed: true,
+            total_violations: 5,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result);
+        assert!(output.contains("no remaining headroom"));
+    }
+
+    #[test]
+    fn test_strict_mode_does_not_flag_rules_with_headroom() {
+        let formatter = HumanFormatter::new(ColorChoice::Never).with_strict(true);
+        let status = create_test_status("no-unwrap", "src", 3, 5, vec![]);
+        let result = AggregationResult {
+            statuses: vec![status],
+            passed: true,
+            total_violations: 3,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result);
+        assert!(!output.contains("no remaining headroom"));
+    }
+
+    #[test]
+    fn test_format_always_colorizes_symbol_location_rule_name_and_snippet() {
+        let formatter = HumanFormatter::new(ColorChoice::Always);
+        let violations = vec![create_test_violation(
+            "no-unwrap",
+            "src/main.rs",
+            "src",
+            10,
+            ".unwrap()",
+        )];
+        let status = create_test_status("no-unwrap", "src", 1, 5, violations);
+        let result = AggregationResult {
+            statuses: vec![status],
+            passed: true,
+            total_violations: 1,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result);
+        assert!(output.contains("no-unwrap"));
+        assert!(output.contains("src/main.rs:10:5"));
+        assert!(output.contains('\u{2713}'));
+        assert!(output.contains('\x1b'));
+        // Stripping every escape sequence should recover the same content `ColorChoice::Never` produces
+        let plain = HumanFormatter::new(ColorChoice::Never).format(&result);
+        let stripped: String = {
+            let mut out = String::new();
+            let mut in_escape = false;
+            for ch in output.chars() {
+                if ch == '\x1b' {
+                    in_escape = true;
+                } else if in_escape {
+                    if ch == 'm' {
+                        in_escape = false;
+                    }
+                } else {
+                    out.push(ch);
+                }
+            }
+            out
+        };
+        assert_eq!(stripped, plain);
+    }
+
+    #[test]
+    fn test_format_never_emits_ansi_codes_even_with_strict_and_terse() {
+        let formatter = HumanFormatter::new(ColorChoice::Never)
+            .with_strict(true)
+            .with_verbosity(Verbosity::Terse);
+        let status = create_test_status("no-unwrap", "src", 5, 5, vec![]);
+        let result = AggregationResult {
+            statuses: vec![status],
+            passed: true,
+            total_violations: 5,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result);
+        assert!(!output.contains('\x1b'));
+        assert!(output.contains("no remaining headroom"));
+    }
 }
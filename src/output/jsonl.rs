@@ -4,22 +4,122 @@
 //!
 //! Outputs one JSON object per line in a deterministic order:
 //! 1. All violation records (sorted by rule, file, line)
-//! 2. All summary records (sorted by rule, region)
+//! 2. All summary records (sorted by severity, rule, region)
 //! 3. One status record
-
-use crate::engine::aggregator::AggregationResult;
-use serde::Serialize;
+//!
+//! Summaries carry a `severity`, and the status record's `passed` only
+//! reflects [`Severity::Error`] rules — consumers that need an
+//! enforcement decision should key off `StatusRecord::exit_code` rather
+//! than `passed`, since a non-zero exit code also accounts for
+//! `warnings_exceeded` depending on how the caller wants to treat them.
+//!
+//! [`JsonDocumentFormatter`] emits the same three record types as a single
+//! pretty-printed JSON document instead of one-object-per-line, for
+//! consumers that would rather parse the whole report at once.
+//!
+//! [`JsonFormatter`] emits a different, rule-centric shape instead of the
+//! record-oriented one above: a top-level `passed`/`total_violations`/
+//! `violations_over_budget` summary plus a `rules` array, one entry per
+//! (rule, region) pair, each carrying its own nested `violations`. It's the
+//! shape CI tooling that groups output by rule tends to want, and also
+//! exposes [`JsonFormatter::write`] for streaming straight to an `io::Write`
+//! instead of buffering a `String` first.
+//!
+//! [`JsonlFormatter`], [`JsonDocumentFormatter`], and [`JsonFormatter`] all
+//! implement the shared [`Formatter`] trait so a caller can select one by
+//! name without matching on a format enum itself.
+//!
+//! [`JsonlFormatter::schema`] publishes the record shapes as a JSON Schema,
+//! and [`validate_output`] checks a formatted report against it — useful
+//! both as an external contract for consumers and as an internal
+//! regression guard against record-shape drift.
+//!
+//! [`GitHubMarkdownFormatter`], [`RatioFormatter`], and
+//! [`GitHubActionsFormatter`] round out the set of formatters a `--format`
+//! flag would dispatch to alongside [`JsonFormatter`], [`JsonDocumentFormatter`],
+//! and [`crate::output::human::HumanFormatter`]. That dispatch itself wants
+//! an `OutputFormat` enum, and [`crate::config`] already re-exports a type by
+//! that name from its `ratchet_toml` submodule — but `ratchet_toml` has no
+//! file in this checkout, so there's nowhere to add the enum without
+//! fabricating the module it's meant to live in. The formatter structs here
+//! are written so that whoever adds `ratchet_toml::OutputFormat` can match on
+//! it and construct one of these with no further changes needed on this end.
+
+use crate::engine::aggregator::{
+    AggregationResult, DEFAULT_TOP_K, RuleRegionStatus, Severity, top_offenders,
+};
+use crate::rules::Violation;
+use crate::types::{RegionPath, RuleId};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::{DefaultHasher, Entry};
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::io::{self, Write};
 use std::path::PathBuf;
+use thiserror::Error;
+
+/// Common behavior for every output format ratchet can emit
+///
+/// Lets a call site (CLI `--format` dispatch, in particular) pick a
+/// formatter without matching on a format enum itself — add a new output
+/// format by implementing this trait rather than by threading a new match
+/// arm through every caller.
+pub trait Formatter {
+    /// Formats `result`, including per-violation detail only when `verbose` is true
+    fn format(&self, result: &AggregationResult, verbose: bool) -> String;
+}
 
 /// JSONL output formatter
 ///
 /// Formats aggregation results as JSON Lines (one JSON object per line).
-pub struct JsonlFormatter;
+pub struct JsonlFormatter {
+    /// See [`JsonlFormatter::with_max_snippet_bytes`]
+    max_snippet_bytes: Option<usize>,
+}
 
 impl JsonlFormatter {
     /// Creates a new JsonlFormatter
     pub fn new() -> Self {
-        JsonlFormatter
+        JsonlFormatter {
+            max_snippet_bytes: None,
+        }
+    }
+
+    /// Truncates snippets over `max_bytes` at a UTF-8 boundary before
+    /// serializing them
+    ///
+    /// A truncated snippet gets an ellipsis appended and its violation
+    /// record's `truncated` field set to `true`, with `snippet_bytes`
+    /// reporting the untruncated length — large monorepos running rules
+    /// against minified or generated files can otherwise produce
+    /// multi-megabyte JSONL lines that choke downstream parsers.
+    pub fn with_max_snippet_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_snippet_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Renders `snippet` for a [`ViolationRecord`], truncating it to
+    /// [`JsonlFormatter::with_max_snippet_bytes`]'s limit when set
+    ///
+    /// Returns `(rendered snippet, was truncated, original byte length)`.
+    /// Checks the raw byte length first so the common case — a snippet
+    /// under the limit, or no limit configured — never allocates.
+    fn render_snippet(&self, snippet: &str) -> (String, bool, usize) {
+        let original_bytes = snippet.len();
+        let Some(max_bytes) = self.max_snippet_bytes else {
+            return (snippet.to_string(), false, original_bytes);
+        };
+        if original_bytes <= max_bytes {
+            return (snippet.to_string(), false, original_bytes);
+        }
+
+        let mut boundary = max_bytes;
+        while boundary > 0 && !snippet.is_char_boundary(boundary) {
+            boundary -= 1;
+        }
+        let mut rendered = snippet[..boundary].to_string();
+        rendered.push('…');
+        (rendered, true, original_bytes)
     }
 
     /// Format the aggregation result as JSONL
@@ -29,22 +129,369 @@ impl JsonlFormatter {
     /// - Then: All summary records (sorted by rule, region)
     /// - Finally: One status record
     ///
+    /// A thin wrapper over [`JsonlFormatter::format_to`] for callers that
+    /// want the whole report as one `String`; anything writing a large
+    /// report to a file or socket should call `format_to` directly instead
+    /// of buffering it here first.
+    ///
     /// # Arguments
     ///
     /// * `result` - The aggregation result to format
     /// * `verbose` - If true, output violation records. If false, skip violation records.
     pub fn format(&self, result: &AggregationResult, verbose: bool) -> String {
-        let mut output = String::new();
+        let mut buffer = Vec::new();
+        self.format_to(result, verbose, &mut buffer)
+            .expect("writing to a Vec<u8> never fails");
+        String::from_utf8(buffer).expect("records serialize to valid UTF-8 JSON")
+    }
 
-        // Only output violation records if verbose is true
+    /// Streams the aggregation result as JSONL directly to `writer`
+    ///
+    /// Same record order and sort as [`JsonlFormatter::format`], but each
+    /// record is serialized straight to `writer` instead of being collected
+    /// into an owned `String` first — on a monorepo with hundreds of
+    /// thousands of violations that avoids a multi-hundred-MB intermediate
+    /// allocation. To keep that sort from requiring its own clone of every
+    /// record, it sorts `(status_index, violation_index)` pairs by their
+    /// borrowed (rule, file, line) fields and streams in that order.
+    pub fn format_to<W: Write>(
+        &self,
+        result: &AggregationResult,
+        verbose: bool,
+        writer: &mut W,
+    ) -> io::Result<()> {
         if verbose {
-            // Collect all violations from all statuses
-            let mut all_violations: Vec<ViolationRecord> = Vec::new();
-            for status in &result.statuses {
-                for violation in &status.violations {
-                    all_violations.push(ViolationRecord {
-                        record_type: "violation".to_string(),
-                        rule: status.rule_id.as_str().to_string(),
+            let mut indices: Vec<(usize, usize)> = result
+                .statuses
+                .iter()
+                .enumerate()
+                .flat_map(|(status_index, status)| {
+                    (0..status.violations.len())
+                        .map(move |violation_index| (status_index, violation_index))
+                })
+                .collect();
+
+            indices.sort_by(|&(sa, va), &(sb, vb)| {
+                let status_a = &result.statuses[sa];
+                let status_b = &result.statuses[sb];
+                status_a
+                    .rule_id
+                    .as_str()
+                    .cmp(status_b.rule_id.as_str())
+                    .then_with(|| {
+                        status_a.violations[va]
+                            .file
+                            .cmp(&status_b.violations[vb].file)
+                    })
+                    .then_with(|| {
+                        status_a.violations[va]
+                            .line
+                            .cmp(&status_b.violations[vb].line)
+                    })
+            });
+
+            for (status_index, violation_index) in indices {
+                let status = &result.statuses[status_index];
+                let violation = &status.violations[violation_index];
+                let (snippet, truncated, snippet_bytes) = self.render_snippet(&violation.snippet);
+                let record = ViolationRecord {
+                    record_type: "violation".to_string(),
+                    rule: status.rule_id.as_str().to_string(),
+                    file: violation.file.clone(),
+                    line: violation.line,
+                    column: violation.column,
+                    end_line: violation.end_line,
+                    end_column: violation.end_column,
+                    snippet,
+                    message: violation.message.clone(),
+                    region: violation.region.as_str().to_string(),
+                    source: source_for(status),
+                    truncated,
+                    snippet_bytes: snippet_bytes as u64,
+                };
+                serde_json::to_writer(&mut *writer, &record)?;
+                writer.write_all(b"\n")?;
+            }
+        }
+
+        let mut summary_indices: Vec<usize> = (0..result.statuses.len()).collect();
+        summary_indices.sort_by(|&a, &b| {
+            let status_a = &result.statuses[a];
+            let status_b = &result.statuses[b];
+            status_a
+                .severity
+                .cmp(&status_b.severity)
+                .then_with(|| status_a.rule_id.as_str().cmp(status_b.rule_id.as_str()))
+                .then_with(|| status_a.region.as_str().cmp(status_b.region.as_str()))
+        });
+
+        for index in summary_indices {
+            let status = &result.statuses[index];
+            let record = SummaryRecord {
+                record_type: "summary".to_string(),
+                rule: status.rule_id.as_str().to_string(),
+                region: status.region.as_str().to_string(),
+                violations: status.actual_count,
+                budget: status.budget,
+                status: if status.passed { "pass" } else { "fail" }.to_string(),
+                severity: severity_str(status.severity).to_string(),
+                source: source_for(status),
+            };
+            serde_json::to_writer(&mut *writer, &record)?;
+            writer.write_all(b"\n")?;
+        }
+
+        let status = status_record(result);
+        serde_json::to_writer(&mut *writer, &status)?;
+        writer.write_all(b"\n")?;
+
+        Ok(())
+    }
+
+    /// Diffs `current` against a previously emitted `baseline`, classifying
+    /// each violation as `"added"`, `"removed"`, or `"unchanged"`
+    ///
+    /// Violations are matched by (rule, file, snippet) rather than line, so
+    /// an edit that only shifts line numbers elsewhere in the file doesn't
+    /// register as churn. A violation present only in `baseline` is emitted
+    /// as `"removed"`, reconstructed from the baseline record since it no
+    /// longer exists in `current`. Summary records additionally report
+    /// `baseline_violations` and the `delta` against the current count, so a
+    /// reviewer can see whether each rule/region moved toward or away from
+    /// its budget.
+    pub fn format_diff(&self, current: &AggregationResult, baseline: &[Record]) -> String {
+        let mut output = String::new();
+
+        let baseline_violations: Vec<&ViolationRecord> = baseline
+            .iter()
+            .filter_map(|record| match record {
+                Record::Violation(violation) => Some(violation),
+                _ => None,
+            })
+            .collect();
+        let baseline_summaries: HashMap<(&str, &str), &SummaryRecord> = baseline
+            .iter()
+            .filter_map(|record| match record {
+                Record::Summary(summary) => {
+                    Some(((summary.rule.as_str(), summary.region.as_str()), summary))
+                }
+                _ => None,
+            })
+            .collect();
+
+        let mut current_keys: Vec<(&str, &PathBuf, &str)> = Vec::new();
+        let mut diff_violations: Vec<DiffViolationRecord> = Vec::new();
+
+        for status in &current.statuses {
+            let rule = status.rule_id.as_str();
+            for violation in &status.violations {
+                current_keys.push((rule, &violation.file, &violation.snippet));
+
+                let change = if baseline_violations.iter().any(|baseline_violation| {
+                    baseline_violation.rule == rule
+                        && baseline_violation.file == violation.file
+                        && baseline_violation.snippet == violation.snippet
+                }) {
+                    "unchanged"
+                } else {
+                    "added"
+                };
+
+                diff_violations.push(DiffViolationRecord {
+                    record_type: "violation".to_string(),
+                    rule: rule.to_string(),
+                    file: violation.file.clone(),
+                    line: violation.line,
+                    column: violation.column,
+                    end_line: violation.end_line,
+                    end_column: violation.end_column,
+                    snippet: violation.snippet.clone(),
+                    message: violation.message.clone(),
+                    region: violation.region.as_str().to_string(),
+                    change: change.to_string(),
+                });
+            }
+        }
+
+        for baseline_violation in &baseline_violations {
+            let still_present = current_keys.iter().any(|(rule, file, snippet)| {
+                *rule == baseline_violation.rule
+                    && *file == &baseline_violation.file
+                    && *snippet == baseline_violation.snippet
+            });
+            if !still_present {
+                diff_violations.push(DiffViolationRecord {
+                    record_type: "violation".to_string(),
+                    rule: baseline_violation.rule.clone(),
+                    file: baseline_violation.file.clone(),
+                    line: baseline_violation.line,
+                    column: baseline_violation.column,
+                    end_line: baseline_violation.end_line,
+                    end_column: baseline_violation.end_column,
+                    snippet: baseline_violation.snippet.clone(),
+                    message: baseline_violation.message.clone(),
+                    region: baseline_violation.region.clone(),
+                    change: "removed".to_string(),
+                });
+            }
+        }
+
+        diff_violations.sort_by(|a, b| {
+            a.rule
+                .cmp(&b.rule)
+                .then_with(|| a.file.cmp(&b.file))
+                .then_with(|| a.line.cmp(&b.line))
+        });
+
+        for record in diff_violations {
+            if let Ok(json) = serde_json::to_string(&record) {
+                output.push_str(&json);
+                output.push('\n');
+            }
+        }
+
+        let mut summaries: Vec<DiffSummaryRecord> = Vec::new();
+        for status in &current.statuses {
+            let rule = status.rule_id.as_str();
+            let region = status.region.as_str();
+            let baseline_violation_count = baseline_summaries
+                .get(&(rule, region))
+                .map_or(0, |summary| summary.violations);
+
+            summaries.push(DiffSummaryRecord {
+                record_type: "summary".to_string(),
+                rule: rule.to_string(),
+                region: region.to_string(),
+                violations: status.actual_count,
+                budget: status.budget,
+                status: if status.passed { "pass" } else { "fail" }.to_string(),
+                severity: severity_str(status.severity).to_string(),
+                baseline_violations: baseline_violation_count,
+                delta: status.actual_count as i64 - baseline_violation_count as i64,
+            });
+        }
+
+        summaries.sort_by(|a, b| {
+            severity_rank(&a.severity)
+                .cmp(&severity_rank(&b.severity))
+                .then_with(|| a.rule.cmp(&b.rule))
+                .then_with(|| a.region.cmp(&b.region))
+        });
+
+        for summary in summaries {
+            if let Ok(json) = serde_json::to_string(&summary) {
+                output.push_str(&json);
+                output.push('\n');
+            }
+        }
+
+        output
+    }
+
+    /// Formats how `current` changed relative to `baseline`, classifying
+    /// each violation as `"new"`, `"fixed"`, or `"persisted"`
+    ///
+    /// Unlike [`JsonlFormatter::format_diff`] (which matches a parsed JSONL
+    /// baseline by (rule, file, snippet)), this compares two
+    /// [`AggregationResult`]s directly via [`delta_fingerprint`]: a hash of
+    /// `(rule, file, region, normalized_snippet, message)` that deliberately
+    /// excludes `line`/`column` so a violation that only shifted position
+    /// elsewhere in the file still counts as `"persisted"` rather than
+    /// `fixed` + `new`. The overall status fails if *any* violation is
+    /// `"new"`, regardless of whether its rule still has budget left — a
+    /// ratchet should never regress even if the aggregate count hasn't hit
+    /// its ceiling yet.
+    ///
+    /// # Arguments
+    ///
+    /// * `baseline` - The last-known-good result, e.g. loaded by [`parse`]ing
+    ///   a previously emitted JSONL report's `"type":"violation"` lines
+    /// * `current` - The result from this run
+    /// * `verbose` - If true, emit per-violation `"type":"delta"` records.
+    ///   If false, only the per-rule summaries and overall status are emitted.
+    pub fn format_delta(
+        &self,
+        baseline: &AggregationResult,
+        current: &AggregationResult,
+        verbose: bool,
+    ) -> String {
+        let mut output = String::new();
+
+        let baseline_fingerprints: HashSet<u64> = baseline
+            .statuses
+            .iter()
+            .flat_map(|status| {
+                status
+                    .violations
+                    .iter()
+                    .map(move |violation| (status.rule_id.as_str(), violation))
+            })
+            .map(|(rule, violation)| delta_fingerprint(rule, violation))
+            .collect();
+
+        let current_fingerprints: HashSet<u64> = current
+            .statuses
+            .iter()
+            .flat_map(|status| {
+                status
+                    .violations
+                    .iter()
+                    .map(move |violation| (status.rule_id.as_str(), violation))
+            })
+            .map(|(rule, violation)| delta_fingerprint(rule, violation))
+            .collect();
+
+        let mut deltas: Vec<DeltaViolationRecord> = Vec::new();
+        let mut added_by_rule: HashMap<&str, u64> = HashMap::new();
+        let mut removed_by_rule: HashMap<&str, u64> = HashMap::new();
+        let mut new_count = 0u64;
+        let mut fixed_count = 0u64;
+        let mut persisted_count = 0u64;
+
+        for status in &current.statuses {
+            let rule = status.rule_id.as_str();
+            for violation in &status.violations {
+                let change = if baseline_fingerprints.contains(&delta_fingerprint(rule, violation))
+                {
+                    persisted_count += 1;
+                    "persisted"
+                } else {
+                    new_count += 1;
+                    *added_by_rule.entry(rule).or_insert(0) += 1;
+                    "new"
+                };
+
+                if verbose {
+                    deltas.push(DeltaViolationRecord {
+                        record_type: "delta".to_string(),
+                        rule: rule.to_string(),
+                        file: violation.file.clone(),
+                        line: violation.line,
+                        column: violation.column,
+                        end_line: violation.end_line,
+                        end_column: violation.end_column,
+                        snippet: violation.snippet.clone(),
+                        message: violation.message.clone(),
+                        region: violation.region.as_str().to_string(),
+                        change: change.to_string(),
+                    });
+                }
+            }
+        }
+
+        for status in &baseline.statuses {
+            let rule = status.rule_id.as_str();
+            for violation in &status.violations {
+                if current_fingerprints.contains(&delta_fingerprint(rule, violation)) {
+                    continue;
+                }
+                fixed_count += 1;
+                *removed_by_rule.entry(rule).or_insert(0) += 1;
+
+                if verbose {
+                    deltas.push(DeltaViolationRecord {
+                        record_type: "delta".to_string(),
+                        rule: rule.to_string(),
                         file: violation.file.clone(),
                         line: violation.line,
                         column: violation.column,
@@ -53,61 +500,59 @@ impl JsonlFormatter {
                         snippet: violation.snippet.clone(),
                         message: violation.message.clone(),
                         region: violation.region.as_str().to_string(),
+                        change: "fixed".to_string(),
                     });
                 }
             }
+        }
 
-            // Sort violations by rule, then file, then line
-            all_violations.sort_by(|a, b| {
+        if verbose {
+            deltas.sort_by(|a, b| {
                 a.rule
                     .cmp(&b.rule)
                     .then_with(|| a.file.cmp(&b.file))
                     .then_with(|| a.line.cmp(&b.line))
             });
 
-            // Output all violation records
-            for violation in all_violations {
-                if let Ok(json) = serde_json::to_string(&violation) {
+            for record in deltas {
+                if let Ok(json) = serde_json::to_string(&record) {
                     output.push_str(&json);
                     output.push('\n');
                 }
             }
         }
 
-        // Collect all summary records
-        let mut summaries: Vec<SummaryRecord> = Vec::new();
-        for status in &result.statuses {
-            summaries.push(SummaryRecord {
-                record_type: "summary".to_string(),
-                rule: status.rule_id.as_str().to_string(),
-                region: status.region.as_str().to_string(),
-                violations: status.actual_count,
-                budget: status.budget,
-                status: if status.passed { "pass" } else { "fail" }.to_string(),
-            });
-        }
-
-        // Sort summaries by rule, then region
-        summaries.sort_by(|a, b| a.rule.cmp(&b.rule).then_with(|| a.region.cmp(&b.region)));
-
-        // Output all summary records
-        for summary in summaries {
+        let mut rules: Vec<&str> = added_by_rule
+            .keys()
+            .chain(removed_by_rule.keys())
+            .copied()
+            .collect();
+        rules.sort_unstable();
+        rules.dedup();
+
+        for rule in rules {
+            let added = added_by_rule.get(rule).copied().unwrap_or(0);
+            let removed = removed_by_rule.get(rule).copied().unwrap_or(0);
+            let summary = DeltaSummaryRecord {
+                record_type: "delta_summary".to_string(),
+                rule: rule.to_string(),
+                added,
+                removed,
+                net: added as i64 - removed as i64,
+            };
             if let Ok(json) = serde_json::to_string(&summary) {
                 output.push_str(&json);
                 output.push('\n');
             }
         }
 
-        // Output status record
-        let rules_exceeded = result.statuses.iter().filter(|s| !s.passed).count() as u64;
-        let status = StatusRecord {
-            record_type: "status".to_string(),
-            passed: result.passed,
-            rules_checked: result.statuses.len() as u64,
-            rules_exceeded,
-            total_violations: result.total_violations as u64,
+        let status = DeltaStatusRecord {
+            record_type: "delta_status".to_string(),
+            passed: new_count == 0,
+            new_violations: new_count,
+            fixed_violations: fixed_count,
+            persisted_violations: persisted_count,
         };
-
         if let Ok(json) = serde_json::to_string(&status) {
             output.push_str(&json);
             output.push('\n');
@@ -115,6 +560,42 @@ impl JsonlFormatter {
 
         output
     }
+
+    /// Formats the merge of `results` as JSONL
+    ///
+    /// See [`merge`] for how the shards are combined.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MergeError::BudgetConflict`] if the same (rule, region)
+    /// pair carries different budgets across shards.
+    pub fn format_combined(
+        &self,
+        results: &[AggregationResult],
+        verbose: bool,
+    ) -> Result<String, MergeError> {
+        Ok(self.format(&merge(results)?, verbose))
+    }
+
+    /// Returns a JSON Schema (draft-07) describing the `violation`,
+    /// `summary`, and `status` record shapes [`JsonlFormatter::format`] emits
+    ///
+    /// Gives consumers without access to rustdoc a contract to code
+    /// against, and backs [`validate_output`]'s regression guard — any
+    /// record-shape drift (a renamed field, a field that silently became
+    /// optional) fails there rather than surfacing downstream as a
+    /// confusing parse error in a consumer.
+    pub fn schema() -> serde_json::Value {
+        serde_json::json!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "title": "ratchet JSONL output record",
+            "oneOf": [
+                record_schema("violation", VIOLATION_FIELDS),
+                record_schema("summary", SUMMARY_FIELDS),
+                record_schema("status", STATUS_FIELDS),
+            ],
+        })
+    }
 }
 
 impl Default for JsonlFormatter {
@@ -123,128 +604,2440 @@ impl Default for JsonlFormatter {
     }
 }
 
-/// Violation record for JSONL output
-#[derive(Debug, Serialize)]
-struct ViolationRecord {
-    #[serde(rename = "type")]
-    record_type: String,
-    rule: String,
-    file: PathBuf,
-    line: u32,
-    column: u32,
-    end_line: u32,
-    end_column: u32,
-    snippet: String,
-    message: String,
-    region: String,
+impl Formatter for JsonlFormatter {
+    fn format(&self, result: &AggregationResult, verbose: bool) -> String {
+        JsonlFormatter::format(self, result, verbose)
+    }
 }
 
-/// Summary record for JSONL output
-#[derive(Debug, Serialize)]
-struct SummaryRecord {
-    #[serde(rename = "type")]
-    record_type: String,
-    rule: String,
-    region: String,
-    violations: u64,
-    budget: u64,
-    status: String,
+/// Renders a [`Severity`] the way it's spelled in JSONL records
+fn severity_str(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "info",
+    }
 }
 
-/// Status record for JSONL output
-#[derive(Debug, Serialize)]
-struct StatusRecord {
-    #[serde(rename = "type")]
-    record_type: String,
-    passed: bool,
-    rules_checked: u64,
-    rules_exceeded: u64,
-    total_violations: u64,
+/// Ranks a [`severity_str`]-rendered severity most-severe-first, for sorting
+/// records that only carry the rendered string rather than [`Severity`] itself
+fn severity_rank(severity: &str) -> u8 {
+    match severity {
+        "error" => 0,
+        "warning" => 1,
+        _ => 2,
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::engine::aggregator::RuleRegionStatus;
-    use crate::rules::Violation;
-    use crate::types::{RegionPath, RuleId};
-    use std::path::PathBuf;
+/// Content fingerprint used by [`JsonlFormatter::format_delta`] to match a
+/// violation across two [`AggregationResult`]s
+///
+/// Hashes `(rule, file, region, normalized_snippet, message)`, deliberately
+/// excluding `line`/`column` so a violation that only shifted position
+/// (rather than changed) still fingerprints the same.
+fn delta_fingerprint(rule: &str, violation: &Violation) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    rule.hash(&mut hasher);
+    violation.file.hash(&mut hasher);
+    violation.region.as_str().hash(&mut hasher);
+    violation.snippet.trim().hash(&mut hasher);
+    violation.message.hash(&mut hasher);
+    hasher.finish()
+}
 
-    fn create_test_violation(
-        rule_id: &str,
-        file_path: &str,
-        region: &str,
-        line: u32,
-        column: u32,
-        snippet: &str,
-        message: &str,
-    ) -> Violation {
-        Violation {
-            rule_id: RuleId::new(rule_id).unwrap(),
-            file: PathBuf::from(file_path),
-            line,
-            column,
-            end_line: line,
-            end_column: column + 10,
-            snippet: snippet.to_string(),
-            message: message.to_string(),
-            region: RegionPath::new(region),
-        }
+/// Computes [`StatusRecord::exit_code`] from how many rules exceeded their budget at each severity
+///
+/// `0` when nothing exceeded budget, `1` when any [`Severity::Error`] rule
+/// did (a hard CI failure), `2` when only [`Severity::Warning`]/
+/// [`Severity::Info`] rules did (still worth a non-zero exit for a
+/// stricter pipeline, but distinct from an outright failure).
+fn exit_code(errors_exceeded: u64, warnings_exceeded: u64) -> u8 {
+    if errors_exceeded > 0 {
+        1
+    } else if warnings_exceeded > 0 {
+        2
+    } else {
+        0
     }
+}
 
-    fn create_test_status(
-        rule_id: &str,
-        region: &str,
-        actual_count: u64,
-        budget: u64,
-        violations: Vec<Violation>,
-    ) -> RuleRegionStatus {
-        RuleRegionStatus {
-            rule_id: RuleId::new(rule_id).unwrap(),
-            region: RegionPath::new(region),
-            actual_count,
-            budget,
-            passed: actual_count <= budget,
-            violations,
+/// Resolves the config path to report for a violation/summary record
+///
+/// [`AggregationResult::combine`] tags every status with the config path it
+/// came from; a plain single-config [`ViolationAggregator::aggregate`] run
+/// never sets it, so this falls back to the status's first violation's file
+/// so single-config output still carries *some* provenance.
+fn source_for(status: &RuleRegionStatus) -> String {
+    if !status.source.is_empty() {
+        return status.source.clone();
+    }
+    status
+        .violations
+        .first()
+        .map(|violation| violation.file.display().to_string())
+        .unwrap_or_default()
+}
+
+/// Resolves [`StatusRecord::source`] from every status in `result`
+///
+/// A single path if every status's [`source_for`] agrees, otherwise
+/// `"combined"` — as after [`AggregationResult::combine`] has merged more
+/// than one config, but also for a plain single-config run whose statuses
+/// fall back to different violation files.
+fn overall_source(result: &AggregationResult) -> String {
+    let mut sources: Vec<String> = result.statuses.iter().map(source_for).collect();
+    sources.sort();
+    sources.dedup();
+    match sources.len() {
+        0 => String::new(),
+        1 => sources.remove(0),
+        _ => "combined".to_string(),
+    }
+}
+
+/// Errors from [`merge`]ing sharded [`AggregationResult`]s
+#[derive(Debug, Error)]
+pub enum MergeError {
+    /// The same (rule, region) pair had different budgets across shards
+    #[error(
+        "budget conflict for rule '{rule}' in region '{region}': {first} vs {second} \
+         (shards must share the same budget configuration)"
+    )]
+    BudgetConflict {
+        rule: String,
+        region: String,
+        first: u64,
+        second: u64,
+    },
+}
+
+/// Merges multiple sharded [`AggregationResult`]s into one canonical result
+///
+/// For each (rule_id, region) key present in any shard, sums `actual_count`
+/// and `raw_count` and concatenates `violations`, then recomputes `passed`
+/// and `over_budget` against the shared budget (plain `actual <= budget`,
+/// since a merged result no longer has access to the [`EnforcementPolicy`]
+/// the shards were each aggregated with). Top offenders are recomputed from
+/// the combined violation list. Budgets for the same key must agree across
+/// shards — an inconsistent budget means the shards were configured
+/// differently, which is treated as an error rather than silently picking one.
+///
+/// [`EnforcementPolicy`]: crate::engine::aggregator::EnforcementPolicy
+pub fn merge(results: &[AggregationResult]) -> Result<AggregationResult, MergeError> {
+    let mut merged: HashMap<(RuleId, RegionPath), RuleRegionStatus> = HashMap::new();
+
+    for result in results {
+        for status in &result.statuses {
+            let key = (status.rule_id.clone(), status.region.clone());
+            match merged.entry(key) {
+                Entry::Vacant(entry) => {
+                    entry.insert(status.clone());
+                }
+                Entry::Occupied(mut entry) => {
+                    let existing = entry.get_mut();
+                    if existing.budget != status.budget {
+                        return Err(MergeError::BudgetConflict {
+                            rule: status.rule_id.as_str().to_string(),
+                            region: status.region.as_str().to_string(),
+                            first: existing.budget,
+                            second: status.budget,
+                        });
+                    }
+                    existing.raw_count += status.raw_count;
+                    existing.actual_count += status.actual_count;
+                    existing
+                        .violations
+                        .extend(status.violations.iter().cloned());
+                }
+            }
         }
     }
 
-    #[test]
-    fn test_format_empty_result() {
-        let formatter = JsonlFormatter::new();
-        let result = AggregationResult {
-            statuses: vec![],
-            passed: true,
-            total_violations: 0,
-            violations_over_budget: 0,
-        };
+    let mut statuses: Vec<RuleRegionStatus> = merged.into_values().collect();
+    for status in &mut statuses {
+        status.over_budget = status.actual_count.saturating_sub(status.budget);
+        status.passed = status.over_budget == 0;
+        status.top_offenders = top_offenders(&status.violations, DEFAULT_TOP_K);
+    }
 
-        let output = formatter.format(&result, true);
+    statuses.sort_by(|a, b| {
+        a.rule_id
+            .as_str()
+            .cmp(b.rule_id.as_str())
+            .then_with(|| a.region.as_str().cmp(b.region.as_str()))
+    });
+
+    let total_violations = statuses.iter().map(|s| s.violations.len()).sum();
+    let violations_over_budget = statuses.iter().map(|s| s.over_budget as usize).sum();
+    let passed = statuses
+        .iter()
+        .all(|s| s.passed || s.severity != Severity::Error);
+
+    Ok(AggregationResult {
+        statuses,
+        passed,
+        total_violations,
+        violations_over_budget,
+    })
+}
 
-        // Should only contain status record
-        let lines: Vec<&str> = output.lines().collect();
-        assert_eq!(lines.len(), 1);
+/// SARIF 2.1.0 output formatter for code-scanning integration
+///
+/// Serializes an [`AggregationResult`] into a single-run SARIF log so CI
+/// platforms that ingest the format (GitHub code scanning, etc.) can surface
+/// ratchet findings directly, rather than going through a custom JSONL parser.
+pub struct SarifFormatter;
 
-        // Parse and verify status record
-        let status: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
-        assert_eq!(status["type"], "status");
-        assert_eq!(status["passed"], true);
-        assert_eq!(status["rules_checked"], 0);
-        assert_eq!(status["rules_exceeded"], 0);
-        assert_eq!(status["total_violations"], 0);
+impl SarifFormatter {
+    /// Creates a new SarifFormatter
+    pub fn new() -> Self {
+        SarifFormatter
     }
 
-    #[test]
-    fn test_format_single_violation() {
-        let formatter = JsonlFormatter::new();
-        let violations = vec![create_test_violation(
-            "no-unwrap",
-            "src/main.rs",
-            "src",
-            10,
-            5,
-            ".unwrap()",
-            "Disallow .unwrap() calls",
+    /// Format the aggregation result as a SARIF 2.1.0 log
+    ///
+    /// `tool.driver.rules[]` is the distinct `rule_id`s seen across
+    /// `result.statuses`; `results[]` has one entry per violation, sorted the
+    /// same way [`JsonlFormatter::format`] sorts violation records (by rule,
+    /// then file, then line) so the output is deterministic.
+    pub fn format(&self, result: &AggregationResult) -> String {
+        let mut rule_ids: Vec<&str> = result
+            .statuses
+            .iter()
+            .map(|status| status.rule_id.as_str())
+            .collect();
+        rule_ids.sort_unstable();
+        rule_ids.dedup();
+
+        let rules: Vec<SarifRule> = rule_ids
+            .into_iter()
+            .map(|id| SarifRule { id: id.to_string() })
+            .collect();
+
+        let mut entries: Vec<(&str, &'static str, &Violation)> = Vec::new();
+        for status in &result.statuses {
+            let level = if status.passed { "warning" } else { "error" };
+            for violation in &status.violations {
+                entries.push((status.rule_id.as_str(), level, violation));
+            }
+        }
+        entries.sort_by(|(rule_a, _, a), (rule_b, _, b)| {
+            rule_a
+                .cmp(rule_b)
+                .then_with(|| a.file.cmp(&b.file))
+                .then_with(|| a.line.cmp(&b.line))
+        });
+
+        let results: Vec<SarifResult> = entries
+            .into_iter()
+            .map(|(rule_id, level, violation)| SarifResult {
+                rule_id: rule_id.to_string(),
+                level: level.to_string(),
+                message: SarifMessage {
+                    text: violation.message.clone(),
+                },
+                locations: vec![SarifLocation {
+                    physical_location: SarifPhysicalLocation {
+                        artifact_location: SarifArtifactLocation {
+                            uri: violation.file.to_string_lossy().into_owned(),
+                        },
+                        region: SarifRegion {
+                            start_line: violation.line,
+                            start_column: violation.column,
+                            end_line: violation.end_line,
+                            end_column: violation.end_column,
+                            snippet: SarifSnippet {
+                                text: violation.snippet.clone(),
+                            },
+                        },
+                    },
+                }],
+                partial_fingerprints: SarifFingerprints {
+                    ratchet_v1: sarif_fingerprint(rule_id, &violation.file, &violation.snippet),
+                },
+            })
+            .collect();
+
+        let log = SarifLog {
+            schema: "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/master/Schemata/sarif-schema-2.1.0.json".to_string(),
+            version: "2.1.0".to_string(),
+            runs: vec![SarifRun {
+                tool: SarifTool {
+                    driver: SarifDriver {
+                        name: "ratchet".to_string(),
+                        rules,
+                    },
+                },
+                results,
+            }],
+        };
+
+        serde_json::to_string_pretty(&log).unwrap_or_default()
+    }
+}
+
+impl Default for SarifFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Single-document JSON output formatter
+///
+/// Unlike [`JsonlFormatter`], which emits one JSON object per line,
+/// `JsonDocumentFormatter` emits a single pretty-printed `{ "violations": [...],
+/// "summaries": [...], "status": {...} }` object — convenient for consumers
+/// that want to load the whole report with one `serde_json::from_str` call
+/// (dashboards, test snapshots, `jq -s` pipelines) instead of a
+/// line-by-line JSONL parser. Sorts the same way [`JsonlFormatter::format`]
+/// does: violations by rule, then file, then line; summaries by severity,
+/// then rule, then region.
+pub struct JsonDocumentFormatter;
+
+impl JsonDocumentFormatter {
+    /// Creates a new JsonDocumentFormatter
+    pub fn new() -> Self {
+        JsonDocumentFormatter
+    }
+}
+
+impl Default for JsonDocumentFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Formatter for JsonDocumentFormatter {
+    fn format(&self, result: &AggregationResult, verbose: bool) -> String {
+        let document = JsonDocument {
+            violations: if verbose {
+                violation_records(result)
+            } else {
+                Vec::new()
+            },
+            summaries: summary_records(result),
+            status: status_record(result),
+        };
+        serde_json::to_string_pretty(&document).unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct JsonDocument {
+    violations: Vec<ViolationRecord>,
+    summaries: Vec<SummaryRecord>,
+    status: StatusRecord,
+}
+
+/// Builds every violation record across `result`, sorted by rule, then file, then line
+fn violation_records(result: &AggregationResult) -> Vec<ViolationRecord> {
+    let mut records: Vec<ViolationRecord> = result
+        .statuses
+        .iter()
+        .flat_map(|status| {
+            status
+                .violations
+                .iter()
+                .map(move |violation| ViolationRecord {
+                    record_type: "violation".to_string(),
+                    rule: status.rule_id.as_str().to_string(),
+                    file: violation.file.clone(),
+                    line: violation.line,
+                    column: violation.column,
+                    end_line: violation.end_line,
+                    end_column: violation.end_column,
+                    snippet: violation.snippet.clone(),
+                    message: violation.message.clone(),
+                    region: violation.region.as_str().to_string(),
+                    source: source_for(status),
+                    truncated: false,
+                    snippet_bytes: violation.snippet.len() as u64,
+                })
+        })
+        .collect();
+
+    records.sort_by(|a, b| {
+        a.rule
+            .cmp(&b.rule)
+            .then_with(|| a.file.cmp(&b.file))
+            .then_with(|| a.line.cmp(&b.line))
+    });
+    records
+}
+
+/// Builds every summary record across `result`, sorted by severity, then rule, then region
+fn summary_records(result: &AggregationResult) -> Vec<SummaryRecord> {
+    let mut statuses: Vec<&RuleRegionStatus> = result.statuses.iter().collect();
+    statuses.sort_by(|a, b| {
+        a.severity
+            .cmp(&b.severity)
+            .then_with(|| a.rule_id.as_str().cmp(b.rule_id.as_str()))
+            .then_with(|| a.region.as_str().cmp(b.region.as_str()))
+    });
+
+    statuses
+        .into_iter()
+        .map(|status| SummaryRecord {
+            record_type: "summary".to_string(),
+            rule: status.rule_id.as_str().to_string(),
+            region: status.region.as_str().to_string(),
+            violations: status.actual_count,
+            budget: status.budget,
+            status: if status.passed { "pass" } else { "fail" }.to_string(),
+            severity: severity_str(status.severity).to_string(),
+            source: source_for(status),
+        })
+        .collect()
+}
+
+/// Builds the single status record summarizing `result`, shared by
+/// [`JsonlFormatter::format_to`] and [`JsonDocumentFormatter`]
+fn status_record(result: &AggregationResult) -> StatusRecord {
+    let rules_exceeded = result.statuses.iter().filter(|s| !s.passed).count() as u64;
+    let errors_exceeded = result
+        .statuses
+        .iter()
+        .filter(|s| !s.passed && s.severity == Severity::Error)
+        .count() as u64;
+    let warnings_exceeded = rules_exceeded - errors_exceeded;
+
+    StatusRecord {
+        record_type: "status".to_string(),
+        passed: result.passed,
+        rules_checked: result.statuses.len() as u64,
+        rules_exceeded,
+        errors_exceeded,
+        warnings_exceeded,
+        total_violations: result.total_violations as u64,
+        exit_code: exit_code(errors_exceeded, warnings_exceeded),
+        source: overall_source(result),
+    }
+}
+
+/// Rule-centric JSON output formatter
+///
+/// Unlike [`JsonDocumentFormatter`]'s flat `{ "violations": [...], "summaries":
+/// [...], "status": {...} }`, `JsonFormatter` groups each (rule, region)
+/// pair's own violations underneath it: `{ "passed", "total_violations",
+/// "violations_over_budget", "rules": [{ "rule_id", "region",
+/// "actual_count", "budget", "passed", "violations": [...] }] }`. `rules` is
+/// sorted by `rule_id` then `region`, and each entry's `violations` by file
+/// then line, so a diff between two runs is stable.
+pub struct JsonFormatter;
+
+impl JsonFormatter {
+    /// Creates a new JsonFormatter
+    pub fn new() -> Self {
+        JsonFormatter
+    }
+
+    fn document(&self, result: &AggregationResult, verbose: bool) -> JsonRulesDocument {
+        let mut statuses: Vec<&RuleRegionStatus> = result.statuses.iter().collect();
+        statuses.sort_by(|a, b| {
+            a.rule_id
+                .as_str()
+                .cmp(b.rule_id.as_str())
+                .then_with(|| a.region.as_str().cmp(b.region.as_str()))
+        });
+
+        let rules = statuses
+            .into_iter()
+            .map(|status| {
+                let mut violations: Vec<&Violation> = status.violations.iter().collect();
+                violations.sort_by(|a, b| a.file.cmp(&b.file).then_with(|| a.line.cmp(&b.line)));
+
+                JsonRuleReport {
+                    rule_id: status.rule_id.as_str().to_string(),
+                    region: status.region.as_str().to_string(),
+                    actual_count: status.actual_count,
+                    budget: status.budget,
+                    passed: status.passed,
+                    violations: if verbose {
+                        violations
+                            .into_iter()
+                            .map(|violation| JsonRuleViolation {
+                                file: violation.file.clone(),
+                                line: violation.line,
+                                column: violation.column,
+                                end_line: violation.end_line,
+                                end_column: violation.end_column,
+                                snippet: violation.snippet.clone(),
+                                message: violation.message.clone(),
+                            })
+                            .collect()
+                    } else {
+                        Vec::new()
+                    },
+                }
+            })
+            .collect();
+
+        JsonRulesDocument {
+            passed: result.passed,
+            total_violations: result.total_violations as u64,
+            violations_over_budget: result.violations_over_budget as u64,
+            rules,
+        }
+    }
+
+    /// Serializes `result` straight to `w`, with every rule's violations included
+    ///
+    /// Streams via [`serde_json::to_writer_pretty`] instead of building a
+    /// `String` first, for the same reason [`JsonlFormatter::format_to`]
+    /// does: a large report shouldn't need a second multi-hundred-MB
+    /// allocation just to hand it to a writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `w` fails, or if `result` can't be serialized
+    /// (neither currently happens for well-formed input).
+    pub fn write(&self, result: &AggregationResult, w: &mut dyn Write) -> io::Result<()> {
+        serde_json::to_writer_pretty(w, &self.document(result, true))?;
+        Ok(())
+    }
+}
+
+impl Default for JsonFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Formatter for JsonFormatter {
+    fn format(&self, result: &AggregationResult, verbose: bool) -> String {
+        serde_json::to_string_pretty(&self.document(result, verbose)).unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct JsonRulesDocument {
+    passed: bool,
+    total_violations: u64,
+    violations_over_budget: u64,
+    rules: Vec<JsonRuleReport>,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonRuleReport {
+    rule_id: String,
+    region: String,
+    actual_count: u64,
+    budget: u64,
+    passed: bool,
+    violations: Vec<JsonRuleViolation>,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonRuleViolation {
+    file: PathBuf,
+    line: u32,
+    column: u32,
+    end_line: u32,
+    end_column: u32,
+    snippet: String,
+    message: String,
+}
+
+/// Renders an [`AggregationResult`] as a collapsible Markdown table for pasting into a PR comment
+///
+/// The table has one row per rule/region with Pass/Fail, actual count, and
+/// budget columns, wrapped in a `<details>` block so it collapses by default
+/// in a GitHub comment or check summary; the `<summary>` line gives the
+/// pass/fail counts so a reader doesn't have to expand the table just to see
+/// whether anything failed. Rows are sorted the same way
+/// [`JsonlFormatter::format`] sorts summary records: by severity, then rule,
+/// then region.
+pub struct GitHubMarkdownFormatter;
+
+impl GitHubMarkdownFormatter {
+    /// Creates a new GitHubMarkdownFormatter
+    pub fn new() -> Self {
+        GitHubMarkdownFormatter
+    }
+}
+
+impl Default for GitHubMarkdownFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Formatter for GitHubMarkdownFormatter {
+    fn format(&self, result: &AggregationResult, _verbose: bool) -> String {
+        let mut statuses: Vec<&RuleRegionStatus> = result.statuses.iter().collect();
+        statuses.sort_by(|a, b| {
+            a.severity
+                .cmp(&b.severity)
+                .then_with(|| a.rule_id.as_str().cmp(b.rule_id.as_str()))
+                .then_with(|| a.region.as_str().cmp(b.region.as_str()))
+        });
+
+        let passed_count = statuses.iter().filter(|s| s.passed).count();
+        let failed_count = statuses.len() - passed_count;
+
+        let mut out = String::new();
+        out.push_str("<details>\n");
+        out.push_str(&format!(
+            "<summary>Ratchet results: {} passed, {} failed</summary>\n\n",
+            passed_count, failed_count
+        ));
+        out.push_str("| Rule | Region | Status | Actual | Budget |\n");
+        out.push_str("| --- | --- | --- | --- | --- |\n");
+        for status in &statuses {
+            let mark = if status.passed {
+                "✅ Pass"
+            } else {
+                "❌ Fail"
+            };
+            out.push_str(&format!(
+                "| {} | {} | {} | {} | {} |\n",
+                status.rule_id.as_str(),
+                status.region.as_str(),
+                mark,
+                status.actual_count,
+                status.budget,
+            ));
+        }
+        out.push_str("\n</details>\n");
+        out
+    }
+}
+
+/// Renders an [`AggregationResult`] as a terse one-line-per-rule `actual/budget` summary
+///
+/// Each line is `{rule_id} {region}: {actual}/{budget}`, sorted the same way
+/// [`GitHubMarkdownFormatter`] sorts its table rows. Meant for dashboards or
+/// chat-ops bots that want the ratio at a glance without a whole report —
+/// unlike [`GitHubMarkdownFormatter`] there's no pass/fail marker, since the
+/// ratio itself tells the reader that (`over budget` iff `actual > budget`).
+pub struct RatioFormatter;
+
+impl RatioFormatter {
+    /// Creates a new RatioFormatter
+    pub fn new() -> Self {
+        RatioFormatter
+    }
+}
+
+impl Default for RatioFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Formatter for RatioFormatter {
+    fn format(&self, result: &AggregationResult, _verbose: bool) -> String {
+        let mut statuses: Vec<&RuleRegionStatus> = result.statuses.iter().collect();
+        statuses.sort_by(|a, b| {
+            a.severity
+                .cmp(&b.severity)
+                .then_with(|| a.rule_id.as_str().cmp(b.rule_id.as_str()))
+                .then_with(|| a.region.as_str().cmp(b.region.as_str()))
+        });
+
+        statuses
+            .iter()
+            .map(|status| {
+                format!(
+                    "{} {}: {}/{}",
+                    status.rule_id.as_str(),
+                    status.region.as_str(),
+                    status.actual_count,
+                    status.budget,
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Renders each violation as a [GitHub workflow command](https://docs.github.com/en/actions/using-workflows/workflow-commands-for-github-actions)
+/// so CI annotates the exact line in the PR "Files changed" view
+///
+/// Emits `::error file={path},line={line},col={col}::{rule}: {snippet}` for
+/// a violation belonging to a rule that's over budget, and `::warning ...`
+/// for a violation whose rule still has remaining budget — the level is
+/// per-rule, not per-violation, so a rule with budget to spare never emits
+/// `error` annotations even though every violation still gets reported.
+/// Property values and message text are percent-escaped per the
+/// workflow-command rules (see [`escape_property`] and [`escape_message`]),
+/// since a file path or snippet containing `,`, `:`, or a newline would
+/// otherwise corrupt the command's own field delimiters.
+pub struct GitHubActionsFormatter {
+    /// See [`GitHubActionsFormatter::with_summary`]
+    with_summary: bool,
+}
+
+impl GitHubActionsFormatter {
+    /// Creates a new GitHubActionsFormatter
+    pub fn new() -> Self {
+        GitHubActionsFormatter {
+            with_summary: false,
+        }
+    }
+
+    /// Appends a trailing `::notice::Check PASSED/FAILED` line summarizing
+    /// [`AggregationResult::passed`]
+    pub fn with_summary(mut self) -> Self {
+        self.with_summary = true;
+        self
+    }
+}
+
+impl Default for GitHubActionsFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Formatter for GitHubActionsFormatter {
+    fn format(&self, result: &AggregationResult, _verbose: bool) -> String {
+        let mut statuses: Vec<&RuleRegionStatus> = result.statuses.iter().collect();
+        statuses.sort_by(|a, b| {
+            a.severity
+                .cmp(&b.severity)
+                .then_with(|| a.rule_id.as_str().cmp(b.rule_id.as_str()))
+                .then_with(|| a.region.as_str().cmp(b.region.as_str()))
+        });
+
+        let mut lines: Vec<String> = Vec::new();
+        for status in &statuses {
+            let level = if status.over_budget > 0 {
+                "error"
+            } else {
+                "warning"
+            };
+            for violation in &status.violations {
+                let file = escape_property(&violation.file.to_string_lossy());
+                let message = escape_message(&format!(
+                    "{}: {}",
+                    status.rule_id.as_str(),
+                    violation.snippet
+                ));
+                lines.push(format!(
+                    "::{} file={},line={},col={}::{}",
+                    level, file, violation.line, violation.column, message
+                ));
+            }
+        }
+
+        if self.with_summary {
+            let verdict = if result.passed { "PASSED" } else { "FAILED" };
+            lines.push(format!("::notice::Check {}", verdict));
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// Escapes a GitHub workflow command property value (e.g. `file=...`)
+///
+/// Order matters: `%` must be escaped first, otherwise the `%` introduced by
+/// escaping `\r`/`\n`/`,`/`:` would itself get re-escaped.
+fn escape_property(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+        .replace(',', "%2C")
+        .replace(':', "%3A")
+}
+
+/// Escapes a GitHub workflow command message (the text after the second `::`)
+///
+/// Unlike [`escape_property`], `,` and `:` are left alone — they only need
+/// escaping inside property values, not the free-form message.
+fn escape_message(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// The JSON type a [`JsonlFormatter::schema`] field is declared as
+enum FieldType {
+    Str,
+    Bool,
+    UInt,
+}
+
+impl FieldType {
+    fn json_type(&self) -> &'static str {
+        match self {
+            FieldType::Str => "string",
+            FieldType::Bool => "boolean",
+            FieldType::UInt => "integer",
+        }
+    }
+
+    /// Whether `value` is the shape this field declares, used by [`validate_record`]
+    fn matches(&self, value: &serde_json::Value) -> bool {
+        match self {
+            FieldType::Str => value.is_string(),
+            FieldType::Bool => value.is_boolean(),
+            FieldType::UInt => value.as_u64().is_some(),
+        }
+    }
+}
+
+/// [`ViolationRecord`]'s fields, in [`JsonlFormatter::schema`]/[`validate_record`] order
+const VIOLATION_FIELDS: &[(&str, FieldType)] = &[
+    ("type", FieldType::Str),
+    ("rule", FieldType::Str),
+    ("file", FieldType::Str),
+    ("line", FieldType::UInt),
+    ("column", FieldType::UInt),
+    ("end_line", FieldType::UInt),
+    ("end_column", FieldType::UInt),
+    ("snippet", FieldType::Str),
+    ("message", FieldType::Str),
+    ("region", FieldType::Str),
+    ("source", FieldType::Str),
+    ("truncated", FieldType::Bool),
+    ("snippet_bytes", FieldType::UInt),
+];
+
+/// [`SummaryRecord`]'s fields, in [`JsonlFormatter::schema`]/[`validate_record`] order
+const SUMMARY_FIELDS: &[(&str, FieldType)] = &[
+    ("type", FieldType::Str),
+    ("rule", FieldType::Str),
+    ("region", FieldType::Str),
+    ("violations", FieldType::UInt),
+    ("budget", FieldType::UInt),
+    ("status", FieldType::Str),
+    ("severity", FieldType::Str),
+    ("source", FieldType::Str),
+];
+
+/// [`StatusRecord`]'s fields, in [`JsonlFormatter::schema`]/[`validate_record`] order
+const STATUS_FIELDS: &[(&str, FieldType)] = &[
+    ("type", FieldType::Str),
+    ("passed", FieldType::Bool),
+    ("rules_checked", FieldType::UInt),
+    ("rules_exceeded", FieldType::UInt),
+    ("errors_exceeded", FieldType::UInt),
+    ("warnings_exceeded", FieldType::UInt),
+    ("total_violations", FieldType::UInt),
+    ("exit_code", FieldType::UInt),
+    ("source", FieldType::Str),
+];
+
+/// Builds the JSON Schema object for one record `type`, discriminated by
+/// `record_type` and described by `fields`
+fn record_schema(record_type: &str, fields: &[(&str, FieldType)]) -> serde_json::Value {
+    let mut properties = serde_json::Map::new();
+    let mut required = Vec::new();
+    for (name, field_type) in fields {
+        let schema = if *name == "type" {
+            serde_json::json!({"const": record_type})
+        } else {
+            serde_json::json!({"type": field_type.json_type()})
+        };
+        properties.insert((*name).to_string(), schema);
+        required.push(serde_json::Value::String((*name).to_string()));
+    }
+
+    serde_json::json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    })
+}
+
+/// Errors from [`validate_output`]
+#[derive(Debug, Error)]
+pub enum SchemaError {
+    /// A line wasn't valid JSON at all
+    #[error("line {line} is not valid JSON: {source}")]
+    InvalidJson {
+        line: usize,
+        #[source]
+        source: serde_json::Error,
+    },
+    /// A line parsed as JSON but didn't match [`JsonlFormatter::schema`]
+    #[error("line {line} does not match the record schema: {reason}")]
+    SchemaMismatch { line: usize, reason: String },
+}
+
+/// Parses every line of `output` — as emitted by [`JsonlFormatter::format`]
+/// or [`JsonlFormatter::format_to`] — and asserts each one validates against
+/// [`JsonlFormatter::schema`]
+///
+/// A regression guard analogous to a fixture-based JSON conformance suite:
+/// any record-shape drift is caught here rather than surfacing downstream
+/// as a confusing parse error in a consumer.
+///
+/// # Errors
+///
+/// Returns the first [`SchemaError`] encountered.
+pub fn validate_output(output: &str) -> Result<(), SchemaError> {
+    for (index, line) in output.lines().enumerate() {
+        let line_number = index + 1;
+        let value: serde_json::Value =
+            serde_json::from_str(line).map_err(|source| SchemaError::InvalidJson {
+                line: line_number,
+                source,
+            })?;
+        validate_record(&value).map_err(|reason| SchemaError::SchemaMismatch {
+            line: line_number,
+            reason,
+        })?;
+    }
+    Ok(())
+}
+
+/// Checks `value` against the field list for its `"type"` discriminator
+fn validate_record(value: &serde_json::Value) -> Result<(), String> {
+    let record_type = value
+        .get("type")
+        .and_then(|t| t.as_str())
+        .ok_or_else(|| "missing \"type\" field".to_string())?;
+
+    let fields = match record_type {
+        "violation" => VIOLATION_FIELDS,
+        "summary" => SUMMARY_FIELDS,
+        "status" => STATUS_FIELDS,
+        other => return Err(format!("unknown record type \"{other}\"")),
+    };
+
+    for (name, field_type) in fields {
+        let field_value = value
+            .get(name)
+            .ok_or_else(|| format!("missing \"{name}\" field"))?;
+        if !field_type.matches(field_value) {
+            return Err(format!(
+                "field \"{name}\" is not a {}",
+                field_type.json_type()
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Computes a stable fingerprint for a violation from its rule, file, and
+/// snippet, so re-ordering or re-running ratchet doesn't produce a new SARIF
+/// alert for the same underlying violation.
+fn sarif_fingerprint(rule_id: &str, file: &std::path::Path, snippet: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    rule_id.hash(&mut hasher);
+    file.hash(&mut hasher);
+    snippet.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+#[derive(Debug, Serialize)]
+struct SarifLog {
+    #[serde(rename = "$schema")]
+    schema: String,
+    version: String,
+    runs: Vec<SarifRun>,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifRun {
+    tool: SarifTool,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifTool {
+    driver: SarifDriver,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifDriver {
+    name: String,
+    rules: Vec<SarifRule>,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifRule {
+    id: String,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifResult {
+    #[serde(rename = "ruleId")]
+    rule_id: String,
+    level: String,
+    message: SarifMessage,
+    locations: Vec<SarifLocation>,
+    #[serde(rename = "partialFingerprints")]
+    partial_fingerprints: SarifFingerprints,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifMessage {
+    text: String,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifLocation {
+    #[serde(rename = "physicalLocation")]
+    physical_location: SarifPhysicalLocation,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifPhysicalLocation {
+    #[serde(rename = "artifactLocation")]
+    artifact_location: SarifArtifactLocation,
+    region: SarifRegion,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifArtifactLocation {
+    uri: String,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifRegion {
+    #[serde(rename = "startLine")]
+    start_line: u32,
+    #[serde(rename = "startColumn")]
+    start_column: u32,
+    #[serde(rename = "endLine")]
+    end_line: u32,
+    #[serde(rename = "endColumn")]
+    end_column: u32,
+    snippet: SarifSnippet,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifSnippet {
+    text: String,
+}
+
+#[derive(Debug, Serialize)]
+struct SarifFingerprints {
+    #[serde(rename = "ratchet/v1")]
+    ratchet_v1: String,
+}
+
+/// Violation record for JSONL output
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ViolationRecord {
+    #[serde(rename = "type")]
+    pub record_type: String,
+    pub rule: String,
+    pub file: PathBuf,
+    pub line: u32,
+    pub column: u32,
+    pub end_line: u32,
+    pub end_column: u32,
+    pub snippet: String,
+    pub message: String,
+    pub region: String,
+    /// The config file this violation's rule was checked against, as set by
+    /// [`AggregationResult::combine`]; for a single-config run, where no
+    /// config path is set, the status's first violation's file — see [`source_for`]
+    pub source: String,
+    /// Whether `snippet` was truncated — see [`JsonlFormatter::with_max_snippet_bytes`]
+    pub truncated: bool,
+    /// `snippet`'s byte length before any truncation
+    pub snippet_bytes: u64,
+}
+
+/// Summary record for JSONL output
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SummaryRecord {
+    #[serde(rename = "type")]
+    pub record_type: String,
+    pub rule: String,
+    pub region: String,
+    pub violations: u64,
+    pub budget: u64,
+    pub status: String,
+    /// `"error"`, `"warning"`, or `"info"` — see [`crate::engine::aggregator::Severity`]
+    pub severity: String,
+    /// See [`ViolationRecord::source`]
+    pub source: String,
+}
+
+/// Status record for JSONL output
+///
+/// `passed` only reflects `"error"`-severity rules; a rule graded
+/// `"warning"` or `"info"` can exceed its budget (reflected in
+/// `warnings_exceeded`) without flipping `passed`. Consumers that want a
+/// single enforcement decision should key off `exit_code` rather than
+/// `passed` — see [`exit_code`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusRecord {
+    #[serde(rename = "type")]
+    pub record_type: String,
+    pub passed: bool,
+    pub rules_checked: u64,
+    pub rules_exceeded: u64,
+    /// Number of `"error"`-severity rule/regions over budget
+    pub errors_exceeded: u64,
+    /// Number of `"warning"`/`"info"`-severity rule/regions over budget
+    pub warnings_exceeded: u64,
+    pub total_violations: u64,
+    /// `0` clean, `1` an error-severity rule exceeded budget, `2` only
+    /// warning/info rules did — see [`exit_code`]
+    pub exit_code: u8,
+    /// The one source every status shares; `"combined"` when they differ,
+    /// e.g. after [`AggregationResult::combine`] merged runs from more than
+    /// one config — see [`overall_source`]
+    pub source: String,
+}
+
+/// A record parsed back out of a previously emitted JSONL report, discriminated by its `type` field
+///
+/// See [`parse`].
+#[derive(Debug, Clone)]
+pub enum Record {
+    Violation(ViolationRecord),
+    Summary(SummaryRecord),
+    Status(StatusRecord),
+}
+
+/// Parses a previously emitted JSONL report back into [`Record`]s
+///
+/// Each line is parsed independently and a line whose `type` doesn't match a
+/// known record kind (or that isn't valid JSON at all) is skipped rather
+/// than treated as an error, so a baseline produced by a newer or older
+/// ratchet version doesn't break [`JsonlFormatter::format_diff`].
+pub fn parse(input: &str) -> Vec<Record> {
+    input
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter_map(|value| match value.get("type").and_then(|t| t.as_str()) {
+            Some("violation") => serde_json::from_value(value).ok().map(Record::Violation),
+            Some("summary") => serde_json::from_value(value).ok().map(Record::Summary),
+            Some("status") => serde_json::from_value(value).ok().map(Record::Status),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Violation record emitted by [`JsonlFormatter::format_diff`], additionally
+/// tagged with its status relative to the baseline
+#[derive(Debug, Serialize)]
+struct DiffViolationRecord {
+    #[serde(rename = "type")]
+    record_type: String,
+    rule: String,
+    file: PathBuf,
+    line: u32,
+    column: u32,
+    end_line: u32,
+    end_column: u32,
+    snippet: String,
+    message: String,
+    region: String,
+    /// `"added"`, `"removed"`, or `"unchanged"` relative to the baseline
+    change: String,
+}
+
+/// Summary record emitted by [`JsonlFormatter::format_diff`], additionally
+/// reporting how far this rule/region moved relative to the baseline
+#[derive(Debug, Serialize)]
+struct DiffSummaryRecord {
+    #[serde(rename = "type")]
+    record_type: String,
+    rule: String,
+    region: String,
+    violations: u64,
+    budget: u64,
+    status: String,
+    severity: String,
+    baseline_violations: u64,
+    delta: i64,
+}
+
+/// Violation record emitted by [`JsonlFormatter::format_delta`], tagged with
+/// its classification relative to the baseline
+#[derive(Debug, Serialize)]
+struct DeltaViolationRecord {
+    #[serde(rename = "type")]
+    record_type: String,
+    rule: String,
+    file: PathBuf,
+    line: u32,
+    column: u32,
+    end_line: u32,
+    end_column: u32,
+    snippet: String,
+    message: String,
+    region: String,
+    /// `"new"`, `"fixed"`, or `"persisted"` relative to the baseline
+    change: String,
+}
+
+/// Per-rule summary emitted by [`JsonlFormatter::format_delta`]
+#[derive(Debug, Serialize)]
+struct DeltaSummaryRecord {
+    #[serde(rename = "type")]
+    record_type: String,
+    rule: String,
+    /// Violations present in `current` but not `baseline`
+    added: u64,
+    /// Violations present in `baseline` but not `current`
+    removed: u64,
+    /// `added - removed`
+    net: i64,
+}
+
+/// Overall status emitted by [`JsonlFormatter::format_delta`]
+///
+/// `passed` is `false` if any violation is `"new"`, regardless of whether
+/// its rule still has budget remaining.
+#[derive(Debug, Serialize)]
+struct DeltaStatusRecord {
+    #[serde(rename = "type")]
+    record_type: String,
+    passed: bool,
+    new_violations: u64,
+    fixed_violations: u64,
+    persisted_violations: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::aggregator::RuleRegionStatus;
+    use crate::rules::Violation;
+    use crate::types::{RegionPath, RuleId};
+    use std::path::PathBuf;
+
+    fn create_test_violation(
+        rule_id: &str,
+        file_path: &str,
+        region: &str,
+        line: u32,
+        column: u32,
+        snippet: &str,
+        message: &str,
+    ) -> Violation {
+        Violation {
+            rule_id: RuleId::new(rule_id).unwrap(),
+            file: PathBuf::from(file_path),
+            line,
+            column,
+            end_line: line,
+            end_column: column + 10,
+            snippet: snippet.to_string(),
+            message: message.to_string(),
+            region: RegionPath::new(region),
+        }
+    }
+
+    fn create_test_status(
+        rule_id: &str,
+        region: &str,
+        actual_count: u64,
+        budget: u64,
+        violations: Vec<Violation>,
+    ) -> RuleRegionStatus {
+        RuleRegionStatus {
+            rule_id: RuleId::new(rule_id).unwrap(),
+            region: RegionPath::new(region),
+            actual_count,
+            raw_count: violations.len() as u64,
+            budget,
+            passed: actual_count <= budget,
+            over_budget: actual_count.saturating_sub(budget),
+            severity: Severity::Error,
+            source: String::new(),
+            top_offenders: vec![],
+            violations,
+        }
+    }
+
+    #[test]
+    fn test_format_empty_result() {
+        let formatter = JsonlFormatter::new();
+        let result = AggregationResult {
+            statuses: vec![],
+            passed: true,
+            total_violations: 0,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result, true);
+
+        // Should only contain status record
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 1);
+
+        // Parse and verify status record
+        let status: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(status["type"], "status");
+        assert_eq!(status["passed"], true);
+        assert_eq!(status["rules_checked"], 0);
+        assert_eq!(status["rules_exceeded"], 0);
+        assert_eq!(status["total_violations"], 0);
+    }
+
+    #[test]
+    fn test_format_single_violation() {
+        let formatter = JsonlFormatter::new();
+        let violations = vec![create_test_violation(
+            "no-unwrap",
+            "src/main.rs",
+            "src",
+            10,
+            5,
+            ".unwrap()",
+            "Disallow .unwrap() calls",
+        )];
+        let status = create_test_status("no-unwrap", "src", 1, 5, violations);
+        let result = AggregationResult {
+            statuses: vec![status],
+            passed: true,
+            total_violations: 1,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result, true);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 3); // 1 violation + 1 summary + 1 status
+
+        // Verify violation record
+        let violation: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(violation["type"], "violation");
+        assert_eq!(violation["rule"], "no-unwrap");
+        assert_eq!(violation["file"], "src/main.rs");
+        assert_eq!(violation["line"], 10);
+        assert_eq!(violation["column"], 5);
+        assert_eq!(violation["end_line"], 10);
+        assert_eq!(violation["end_column"], 15);
+        assert_eq!(violation["snippet"], ".unwrap()");
+        assert_eq!(violation["message"], "Disallow .unwrap() calls");
+        assert_eq!(violation["region"], "src");
+
+        // Verify summary record
+        let summary: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(summary["type"], "summary");
+        assert_eq!(summary["rule"], "no-unwrap");
+        assert_eq!(summary["region"], "src");
+        assert_eq!(summary["violations"], 1);
+        assert_eq!(summary["budget"], 5);
+        assert_eq!(summary["status"], "pass");
+
+        // Verify status record
+        let status: serde_json::Value = serde_json::from_str(lines[2]).unwrap();
+        assert_eq!(status["type"], "status");
+        assert_eq!(status["passed"], true);
+        assert_eq!(status["rules_checked"], 1);
+        assert_eq!(status["rules_exceeded"], 0);
+        assert_eq!(status["total_violations"], 1);
+    }
+
+    #[test]
+    fn test_format_falls_back_to_violation_file_when_status_has_no_source() {
+        let formatter = JsonlFormatter::new();
+        let violations = vec![create_test_violation(
+            "no-unwrap",
+            "src/main.rs",
+            "src",
+            10,
+            5,
+            ".unwrap()",
+            "Disallow .unwrap() calls",
+        )];
+        let status = create_test_status("no-unwrap", "src", 1, 5, violations);
+        let result = AggregationResult {
+            statuses: vec![status],
+            passed: true,
+            total_violations: 1,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result, true);
+        let lines: Vec<&str> = output.lines().collect();
+
+        let violation: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(violation["source"], "src/main.rs");
+        let summary: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(summary["source"], "src/main.rs");
+        let status: serde_json::Value = serde_json::from_str(lines[2]).unwrap();
+        assert_eq!(status["source"], "src/main.rs");
+    }
+
+    #[test]
+    fn test_format_reports_status_source_when_combine_tagged_a_single_config() {
+        let formatter = JsonlFormatter::new();
+        let violations = vec![create_test_violation(
+            "no-unwrap",
+            "src/main.rs",
+            "src",
+            10,
+            5,
+            ".unwrap()",
+            "Disallow .unwrap() calls",
+        )];
+        let mut status = create_test_status("no-unwrap", "src", 1, 5, violations);
+        status.source = "ratchet.toml".to_string();
+        let result = AggregationResult {
+            statuses: vec![status],
+            passed: true,
+            total_violations: 1,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result, true);
+        let lines: Vec<&str> = output.lines().collect();
+
+        let violation: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(violation["source"], "ratchet.toml");
+        let status_record: serde_json::Value = serde_json::from_str(lines[2]).unwrap();
+        assert_eq!(status_record["source"], "ratchet.toml");
+    }
+
+    #[test]
+    fn test_format_status_source_is_combined_when_statuses_disagree() {
+        let formatter = JsonlFormatter::new();
+        let mut status_a = create_test_status(
+            "no-unwrap",
+            "a",
+            1,
+            5,
+            vec![create_test_violation(
+                "no-unwrap",
+                "a/main.rs",
+                "a",
+                1,
+                1,
+                "s",
+                "m",
+            )],
+        );
+        status_a.source = "ratchet-a.toml".to_string();
+        let mut status_b = create_test_status(
+            "no-todo",
+            "b",
+            1,
+            5,
+            vec![create_test_violation(
+                "no-todo",
+                "b/main.rs",
+                "b",
+                1,
+                1,
+                "s",
+                "m",
+            )],
+        );
+        status_b.source = "ratchet-b.toml".to_string();
+        let result = AggregationResult {
+            statuses: vec![status_a, status_b],
+            passed: true,
+            total_violations: 2,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result, false);
+        let lines: Vec<&str> = output.lines().collect();
+        let status_record: serde_json::Value = serde_json::from_str(lines.last().unwrap()).unwrap();
+        assert_eq!(status_record["source"], "combined");
+    }
+
+    #[test]
+    fn test_format_multiple_violations_sorted() {
+        let formatter = JsonlFormatter::new();
+
+        // Create violations in unsorted order
+        let violations1 = vec![create_test_violation(
+            "rule-b", "src/z.rs", "src", 20, 5, "snippet2", "message2",
+        )];
+        let violations2 = vec![create_test_violation(
+            "rule-a", "src/a.rs", "src", 10, 5, "snippet1", "message1",
+        )];
+
+        let status1 = create_test_status("rule-b", "src", 1, 5, violations1);
+        let status2 = create_test_status("rule-a", "src", 1, 5, violations2);
+
+        let result = AggregationResult {
+            statuses: vec![status1, status2],
+            passed: true,
+            total_violations: 2,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result, true);
+        let lines: Vec<&str> = output.lines().collect();
+
+        // Verify violations are sorted by rule, then file, then line
+        let v1: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        let v2: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
+
+        assert_eq!(v1["rule"], "rule-a");
+        assert_eq!(v2["rule"], "rule-b");
+
+        // Verify summaries are sorted by rule, then region
+        let s1: serde_json::Value = serde_json::from_str(lines[2]).unwrap();
+        let s2: serde_json::Value = serde_json::from_str(lines[3]).unwrap();
+
+        assert_eq!(s1["rule"], "rule-a");
+        assert_eq!(s2["rule"], "rule-b");
+    }
+
+    #[test]
+    fn test_format_violation_over_budget() {
+        let formatter = JsonlFormatter::new();
+        let violations = vec![
+            create_test_violation(
+                "no-unwrap",
+                "src/main.rs",
+                "src",
+                10,
+                5,
+                ".unwrap()",
+                "Disallow .unwrap() calls",
+            ),
+            create_test_violation(
+                "no-unwrap",
+                "src/lib.rs",
+                "src",
+                20,
+                5,
+                "result.unwrap()",
+                "Disallow .unwrap() calls",
+            ),
+        ];
+        let status = create_test_status("no-unwrap", "src", 2, 1, violations);
+        let result = AggregationResult {
+            statuses: vec![status],
+            passed: false,
+            total_violations: 2,
+            violations_over_budget: 1,
+        };
+
+        let output = formatter.format(&result, true);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 4); // 2 violations + 1 summary + 1 status
+
+        // Verify summary shows fail status
+        let summary: serde_json::Value = serde_json::from_str(lines[2]).unwrap();
+        assert_eq!(summary["status"], "fail");
+        assert_eq!(summary["violations"], 2);
+        assert_eq!(summary["budget"], 1);
+
+        // Verify status record shows failure
+        let status: serde_json::Value = serde_json::from_str(lines[3]).unwrap();
+        assert_eq!(status["passed"], false);
+        assert_eq!(status["rules_exceeded"], 1);
+    }
+
+    #[test]
+    fn test_format_multiple_rules_and_regions() {
+        let formatter = JsonlFormatter::new();
+
+        let violations1 = vec![create_test_violation(
+            "no-unwrap",
+            "src/main.rs",
+            "src",
+            10,
+            5,
+            ".unwrap()",
+            "message",
+        )];
+        let violations2 = vec![create_test_violation(
+            "no-unwrap",
+            "tests/test.rs",
+            "tests",
+            20,
+            5,
+            ".unwrap()",
+            "message",
+        )];
+        let violations3 = vec![create_test_violation(
+            "no-todo",
+            "src/lib.rs",
+            "src",
+            30,
+            5,
+            "// TODO",
+            "message",
+        )];
+
+        let status1 = create_test_status("no-unwrap", "src", 1, 5, violations1);
+        let status2 = create_test_status("no-unwrap", "tests", 1, 10, violations2);
+        let status3 = create_test_status("no-todo", "src", 1, 3, violations3);
+
+        let result = AggregationResult {
+            statuses: vec![status1, status2, status3],
+            passed: true,
+            total_violations: 3,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result, true);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 7); // 3 violations + 3 summaries + 1 status
+
+        // Verify violations are properly sorted
+        let v1: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        let v2: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
+        let v3: serde_json::Value = serde_json::from_str(lines[2]).unwrap();
+
+        assert_eq!(v1["rule"], "no-todo");
+        assert_eq!(v2["rule"], "no-unwrap");
+        assert_eq!(v3["rule"], "no-unwrap");
+
+        // Verify summaries are properly sorted
+        let s1: serde_json::Value = serde_json::from_str(lines[3]).unwrap();
+        let s2: serde_json::Value = serde_json::from_str(lines[4]).unwrap();
+        let s3: serde_json::Value = serde_json::from_str(lines[5]).unwrap();
+
+        assert_eq!(s1["rule"], "no-todo");
+        assert_eq!(s2["rule"], "no-unwrap");
+        assert_eq!(s2["region"], "src");
+        assert_eq!(s3["rule"], "no-unwrap");
+        assert_eq!(s3["region"], "tests");
+    }
+
+    #[test]
+    fn test_json_validity() {
+        let formatter = JsonlFormatter::new();
+        let violations = vec![create_test_violation(
+            "test-rule",
+            "src/test.rs",
+            "src",
+            1,
+            1,
+            "test",
+            "test message",
+        )];
+        let status = create_test_status("test-rule", "src", 1, 1, violations);
+        let result = AggregationResult {
+            statuses: vec![status],
+            passed: true,
+            total_violations: 1,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result, true);
+
+        // Verify each line is valid JSON
+        for line in output.lines() {
+            let parsed: Result<serde_json::Value, _> = serde_json::from_str(line);
+            assert!(parsed.is_ok(), "Invalid JSON: {}", line);
+        }
+    }
+
+    #[test]
+    fn test_default_implementation() {
+        let formatter = JsonlFormatter::default();
+        let result = AggregationResult {
+            statuses: vec![],
+            passed: true,
+            total_violations: 0,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result, true);
+        assert!(!output.is_empty());
+    }
+
+    #[test]
+    fn test_violation_sorting_by_line() {
+        let formatter = JsonlFormatter::new();
+
+        // Create violations with same rule and file but different lines
+        let violations = vec![
+            create_test_violation("rule-a", "src/file.rs", "src", 30, 5, "s3", "m3"),
+            create_test_violation("rule-a", "src/file.rs", "src", 10, 5, "s1", "m1"),
+            create_test_violation("rule-a", "src/file.rs", "src", 20, 5, "s2", "m2"),
+        ];
+
+        let status = create_test_status("rule-a", "src", 3, 5, violations);
+        let result = AggregationResult {
+            statuses: vec![status],
+            passed: true,
+            total_violations: 3,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result, true);
+        let lines: Vec<&str> = output.lines().collect();
+
+        // Verify violations are sorted by line number
+        let v1: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        let v2: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
+        let v3: serde_json::Value = serde_json::from_str(lines[2]).unwrap();
+
+        assert_eq!(v1["line"], 10);
+        assert_eq!(v2["line"], 20);
+        assert_eq!(v3["line"], 30);
+    }
+
+    #[test]
+    fn test_special_characters_in_paths() {
+        let formatter = JsonlFormatter::new();
+
+        // Test with spaces, unicode, and special characters in paths
+        let violations = [
+            create_test_violation(
+                "no-unwrap",
+                "src/my file.rs",
+                "src",
+                10,
+                5,
+                ".unwrap()",
+                "message",
+            ),
+            create_test_violation(
+                "no-todo",
+                "src/日本語.rs",
+                "src",
+                20,
+                5,
+                "// TODO",
+                "message",
+            ),
+            create_test_violation(
+                "no-panic",
+                "src/file's.rs",
+                "src",
+                30,
+                5,
+                "panic!",
+                "message",
+            ),
+        ];
+
+        let status1 = create_test_status("no-unwrap", "src", 1, 5, vec![violations[0].clone()]);
+        let status2 = create_test_status("no-todo", "src", 1, 5, vec![violations[1].clone()]);
+        let status3 = create_test_status("no-panic", "src", 1, 5, vec![violations[2].clone()]);
+
+        let result = AggregationResult {
+            statuses: vec![status1, status2, status3],
+            passed: true,
+            total_violations: 3,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result, true);
+
+        // Verify all lines are valid JSON
+        for line in output.lines() {
+            let parsed: Result<serde_json::Value, _> = serde_json::from_str(line);
+            assert!(parsed.is_ok(), "Invalid JSON: {}", line);
+        }
+
+        // Verify special characters in paths are properly JSON-encoded
+        // Violations are sorted by rule, then file, then line
+        let lines: Vec<&str> = output.lines().collect();
+        let v1: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        let v2: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
+        let v3: serde_json::Value = serde_json::from_str(lines[2]).unwrap();
+
+        // Sorted order: no-panic, no-todo, no-unwrap
+        assert_eq!(v1["rule"], "no-panic");
+        assert_eq!(v1["file"], "src/file's.rs");
+        assert_eq!(v2["rule"], "no-todo");
+        assert_eq!(v2["file"], "src/日本語.rs");
+        assert_eq!(v3["rule"], "no-unwrap");
+        assert_eq!(v3["file"], "src/my file.rs");
+    }
+
+    #[test]
+    fn test_special_characters_in_snippets() {
+        let formatter = JsonlFormatter::new();
+
+        // Test with various special characters in snippets
+        let violations = vec![
+            create_test_violation(
+                "test",
+                "src/test.rs",
+                "src",
+                1,
+                1,
+                "\"hello\\nworld\"",
+                "newline in snippet",
+            ),
+            create_test_violation(
+                "test",
+                "src/test.rs",
+                "src",
+                2,
+                1,
+                "emoji: 🦀",
+                "emoji in snippet",
+            ),
+            create_test_violation(
+                "test",
+                "src/test.rs",
+                "src",
+                3,
+                1,
+                "{\"key\": \"value\"}",
+                "json in snippet",
+            ),
+            create_test_violation(
+                "test",
+                "src/test.rs",
+                "src",
+                4,
+                1,
+                "tab:\there",
+                "tab character",
+            ),
+        ];
+
+        let status = create_test_status("test", "src", 4, 10, violations);
+        let result = AggregationResult {
+            statuses: vec![status],
+            passed: true,
+            total_violations: 4,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result, true);
+
+        // Verify all lines are valid JSON
+        for line in output.lines() {
+            let parsed: Result<serde_json::Value, _> = serde_json::from_str(line);
+            assert!(parsed.is_ok(), "Invalid JSON: {}", line);
+        }
+
+        // Verify special characters are properly escaped
+        let lines: Vec<&str> = output.lines().collect();
+        let v1: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        let v2: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
+        let v3: serde_json::Value = serde_json::from_str(lines[2]).unwrap();
+        let v4: serde_json::Value = serde_json::from_str(lines[3]).unwrap();
+
+        assert_eq!(v1["snippet"], "\"hello\\nworld\"");
+        assert_eq!(v2["snippet"], "emoji: 🦀");
+        assert_eq!(v3["snippet"], "{\"key\": \"value\"}");
+        assert_eq!(v4["snippet"], "tab:\there");
+    }
+
+    #[test]
+    fn test_deterministic_output() {
+        let formatter = JsonlFormatter::new();
+
+        // Create a complex result with multiple violations
+        let violations1 = [
+            create_test_violation("rule-b", "src/z.rs", "src", 20, 5, "snippet2", "message2"),
+            create_test_violation("rule-a", "src/a.rs", "src", 10, 5, "snippet1", "message1"),
+        ];
+        let violations2 = vec![create_test_violation(
+            "rule-c",
+            "tests/test.rs",
+            "tests",
+            30,
+            5,
+            "snippet3",
+            "message3",
+        )];
+
+        let status1 = create_test_status("rule-b", "src", 1, 5, vec![violations1[0].clone()]);
+        let status2 = create_test_status("rule-a", "src", 1, 5, vec![violations1[1].clone()]);
+        let status3 = create_test_status("rule-c", "tests", 1, 5, violations2);
+
+        let result = AggregationResult {
+            statuses: vec![status1, status2, status3],
+            passed: true,
+            total_violations: 3,
+            violations_over_budget: 0,
+        };
+
+        // Format the same result multiple times
+        let output1 = formatter.format(&result, true);
+        let output2 = formatter.format(&result, true);
+        let output3 = formatter.format(&result, true);
+
+        // All outputs should be byte-for-byte identical
+        assert_eq!(output1, output2);
+        assert_eq!(output2, output3);
+
+        // Verify the output is sorted correctly
+        let lines: Vec<&str> = output1.lines().collect();
+
+        // First 3 lines should be violations sorted by rule, file, line
+        let v1: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        let v2: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
+        let v3: serde_json::Value = serde_json::from_str(lines[2]).unwrap();
+
+        assert_eq!(v1["rule"], "rule-a");
+        assert_eq!(v2["rule"], "rule-b");
+        assert_eq!(v3["rule"], "rule-c");
+
+        // Next 3 lines should be summaries sorted by rule, region
+        let s1: serde_json::Value = serde_json::from_str(lines[3]).unwrap();
+        let s2: serde_json::Value = serde_json::from_str(lines[4]).unwrap();
+        let s3: serde_json::Value = serde_json::from_str(lines[5]).unwrap();
+
+        assert_eq!(s1["type"], "summary");
+        assert_eq!(s1["rule"], "rule-a");
+        assert_eq!(s2["type"], "summary");
+        assert_eq!(s2["rule"], "rule-b");
+        assert_eq!(s3["type"], "summary");
+        assert_eq!(s3["rule"], "rule-c");
+
+        // Last line should be status
+        let status: serde_json::Value = serde_json::from_str(lines[6]).unwrap();
+        assert_eq!(status["type"], "status");
+    }
+
+    #[test]
+    fn test_empty_and_whitespace_snippets() {
+        let formatter = JsonlFormatter::new();
+
+        let violations = vec![
+            create_test_violation("test", "src/test.rs", "src", 1, 1, "", "empty"),
+            create_test_violation("test", "src/test.rs", "src", 2, 1, "   ", "whitespace only"),
+            create_test_violation(
+                "test",
+                "src/test.rs",
+                "src",
+                3,
+                1,
+                "\n\n\n",
+                "newlines only",
+            ),
+        ];
+
+        let status = create_test_status("test", "src", 3, 10, violations);
+        let result = AggregationResult {
+            statuses: vec![status],
+            passed: true,
+            total_violations: 3,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result, true);
+
+        // Verify all lines are valid JSON
+        for line in output.lines() {
+            let parsed: Result<serde_json::Value, _> = serde_json::from_str(line);
+            assert!(parsed.is_ok(), "Invalid JSON: {}", line);
+        }
+
+        // Verify snippets are preserved as-is
+        let lines: Vec<&str> = output.lines().collect();
+        let v1: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        let v2: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
+        let v3: serde_json::Value = serde_json::from_str(lines[2]).unwrap();
+
+        assert_eq!(v1["snippet"], "");
+        assert_eq!(v2["snippet"], "   ");
+        assert_eq!(v3["snippet"], "\n\n\n");
+    }
+
+    #[test]
+    fn test_long_snippet_json_encoding() {
+        let formatter = JsonlFormatter::new();
+
+        // Create a very long snippet
+        let long_snippet = "a".repeat(10000);
+        let violations = vec![create_test_violation(
+            "test-rule",
+            "src/test.rs",
+            "src",
+            1,
+            1,
+            &long_snippet,
+            "long snippet",
+        )];
+
+        let status = create_test_status("test-rule", "src", 1, 5, violations);
+        let result = AggregationResult {
+            statuses: vec![status],
+            passed: true,
+            total_violations: 1,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result, true);
+
+        // Verify the line is valid JSON
+        let lines: Vec<&str> = output.lines().collect();
+        let parsed: Result<serde_json::Value, _> = serde_json::from_str(lines[0]);
+        assert!(parsed.is_ok());
+
+        let violation: serde_json::Value = parsed.unwrap();
+        assert_eq!(violation["snippet"], long_snippet);
+    }
+
+    #[test]
+    fn test_with_max_snippet_bytes_truncates_oversized_snippet_at_char_boundary() {
+        let formatter = JsonlFormatter::new().with_max_snippet_bytes(5);
+        // A multi-byte character ('é' is 2 bytes) straddling the 5-byte cut
+        // point must not be split mid-codepoint.
+        let violations = vec![create_test_violation(
+            "test-rule",
+            "src/test.rs",
+            "src",
+            1,
+            1,
+            "abcdé long tail",
+            "long snippet",
+        )];
+        let status = create_test_status("test-rule", "src", 1, 5, violations);
+        let result = AggregationResult {
+            statuses: vec![status],
+            passed: true,
+            total_violations: 1,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result, true);
+        let violation: serde_json::Value =
+            serde_json::from_str(output.lines().next().unwrap()).unwrap();
+
+        assert_eq!(violation["truncated"], true);
+        assert_eq!(violation["snippet_bytes"], "abcdé long tail".len() as u64);
+        assert!(violation["snippet"].as_str().unwrap().starts_with("abcd"));
+        assert!(violation["snippet"].as_str().unwrap().ends_with('…'));
+    }
+
+    #[test]
+    fn test_with_max_snippet_bytes_leaves_short_snippets_untouched() {
+        let formatter = JsonlFormatter::new().with_max_snippet_bytes(100);
+        let violations = vec![create_test_violation(
+            "test-rule",
+            "src/test.rs",
+            "src",
+            1,
+            1,
+            "short",
+            "message",
+        )];
+        let status = create_test_status("test-rule", "src", 1, 5, violations);
+        let result = AggregationResult {
+            statuses: vec![status],
+            passed: true,
+            total_violations: 1,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result, true);
+        let violation: serde_json::Value =
+            serde_json::from_str(output.lines().next().unwrap()).unwrap();
+
+        assert_eq!(violation["snippet"], "short");
+        assert_eq!(violation["truncated"], false);
+        assert_eq!(violation["snippet_bytes"], 5);
+    }
+
+    #[test]
+    fn test_with_max_snippet_bytes_leaves_empty_snippet_untouched() {
+        let formatter = JsonlFormatter::new().with_max_snippet_bytes(1);
+
+        let violations = vec![create_test_violation(
+            "test",
+            "src/test.rs",
+            "src",
+            1,
+            1,
+            "",
+            "empty",
+        )];
+        let status = create_test_status("test", "src", 1, 10, violations);
+        let result = AggregationResult {
+            statuses: vec![status],
+            passed: true,
+            total_violations: 1,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result, true);
+        let violation: serde_json::Value =
+            serde_json::from_str(output.lines().next().unwrap()).unwrap();
+
+        assert_eq!(violation["snippet"], "");
+        assert_eq!(violation["truncated"], false);
+    }
+
+    #[test]
+    fn test_without_max_snippet_bytes_never_truncates() {
+        let formatter = JsonlFormatter::new();
+        let long_snippet = "a".repeat(10_000);
+        let violations = vec![create_test_violation(
+            "test-rule",
+            "src/test.rs",
+            "src",
+            1,
+            1,
+            &long_snippet,
+            "long snippet",
+        )];
+        let status = create_test_status("test-rule", "src", 1, 5, violations);
+        let result = AggregationResult {
+            statuses: vec![status],
+            passed: true,
+            total_violations: 1,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result, true);
+        let violation: serde_json::Value =
+            serde_json::from_str(output.lines().next().unwrap()).unwrap();
+
+        assert_eq!(violation["snippet"], long_snippet);
+        assert_eq!(violation["truncated"], false);
+    }
+
+    #[test]
+    fn test_special_characters_in_messages() {
+        let formatter = JsonlFormatter::new();
+
+        let violations = vec![
+            create_test_violation(
+                "test",
+                "src/test.rs",
+                "src",
+                1,
+                1,
+                "snippet",
+                "message with \"quotes\"",
+            ),
+            create_test_violation(
+                "test",
+                "src/test.rs",
+                "src",
+                2,
+                1,
+                "snippet",
+                "message with 'apostrophe's",
+            ),
+            create_test_violation(
+                "test",
+                "src/test.rs",
+                "src",
+                3,
+                1,
+                "snippet",
+                "message\nwith\nnewlines",
+            ),
+        ];
+
+        let status = create_test_status("test", "src", 3, 10, violations);
+        let result = AggregationResult {
+            statuses: vec![status],
+            passed: true,
+            total_violations: 3,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result, true);
+
+        // Verify all lines are valid JSON
+        for line in output.lines() {
+            let parsed: Result<serde_json::Value, _> = serde_json::from_str(line);
+            assert!(parsed.is_ok(), "Invalid JSON: {}", line);
+        }
+
+        // Verify messages are properly escaped
+        let lines: Vec<&str> = output.lines().collect();
+        let v1: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        let v2: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
+        let v3: serde_json::Value = serde_json::from_str(lines[2]).unwrap();
+
+        assert_eq!(v1["message"], "message with \"quotes\"");
+        assert_eq!(v2["message"], "message with 'apostrophe's");
+        assert_eq!(v3["message"], "message\nwith\nnewlines");
+    }
+
+    #[test]
+    fn test_region_status_with_no_violations() {
+        let formatter = JsonlFormatter::new();
+
+        // Create a status with no violations but positive budget
+        let status = create_test_status("no-unwrap", "src", 0, 5, vec![]);
+        let result = AggregationResult {
+            statuses: vec![status],
+            passed: true,
+            total_violations: 0,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result, true);
+        let lines: Vec<&str> = output.lines().collect();
+
+        // Should have 2 lines: 1 summary + 1 status (no violation records)
+        assert_eq!(lines.len(), 2);
+
+        // Verify summary record
+        let summary: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(summary["type"], "summary");
+        assert_eq!(summary["rule"], "no-unwrap");
+        assert_eq!(summary["violations"], 0);
+        assert_eq!(summary["budget"], 5);
+        assert_eq!(summary["status"], "pass");
+
+        // Verify status record
+        let status: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(status["type"], "status");
+        assert_eq!(status["passed"], true);
+    }
+
+    #[test]
+    fn test_violation_sorting_by_file() {
+        let formatter = JsonlFormatter::new();
+
+        // Create violations with same rule but different files
+        let violations = vec![
+            create_test_violation("rule-a", "src/z.rs", "src", 10, 5, "s1", "m1"),
+            create_test_violation("rule-a", "src/a.rs", "src", 10, 5, "s2", "m2"),
+            create_test_violation("rule-a", "src/m.rs", "src", 10, 5, "s3", "m3"),
+        ];
+
+        let status = create_test_status("rule-a", "src", 3, 5, violations);
+        let result = AggregationResult {
+            statuses: vec![status],
+            passed: true,
+            total_violations: 3,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result, true);
+        let lines: Vec<&str> = output.lines().collect();
+
+        // Verify violations are sorted by file path
+        let v1: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        let v2: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
+        let v3: serde_json::Value = serde_json::from_str(lines[2]).unwrap();
+
+        assert_eq!(v1["file"], "src/a.rs");
+        assert_eq!(v2["file"], "src/m.rs");
+        assert_eq!(v3["file"], "src/z.rs");
+    }
+
+    #[test]
+    fn test_format_non_verbose_hides_violation_records() {
+        // Test that when verbose=false, "type":"violation" records are not output
+        let formatter = JsonlFormatter::new();
+        let violations = vec![
+            create_test_violation(
+                "no-unwrap",
+                "src/main.rs",
+                "src",
+                10,
+                5,
+                ".unwrap()",
+                "Disallow .unwrap() calls",
+            ),
+            create_test_violation(
+                "no-unwrap",
+                "src/lib.rs",
+                "src",
+                20,
+                5,
+                "result.unwrap()",
+                "Disallow .unwrap() calls",
+            ),
+        ];
+        let status = create_test_status("no-unwrap", "src", 2, 5, violations);
+        let result = AggregationResult {
+            statuses: vec![status],
+            passed: true,
+            total_violations: 2,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result, false);
+
+        // Parse each line as JSON
+        let lines: Vec<&str> = output.lines().collect();
+
+        // Assert no lines have "type":"violation"
+        for line in &lines {
+            let parsed: serde_json::Value = serde_json::from_str(line).unwrap();
+            assert_ne!(parsed["type"], "violation");
+        }
+
+        // Assert there ARE lines with "type":"summary"
+        let has_summary = lines.iter().any(|line| {
+            let parsed: serde_json::Value = serde_json::from_str(line).unwrap();
+            parsed["type"] == "summary"
+        });
+        assert!(has_summary);
+
+        // Assert there IS a line with "type":"status"
+        let has_status = lines.iter().any(|line| {
+            let parsed: serde_json::Value = serde_json::from_str(line).unwrap();
+            parsed["type"] == "status"
+        });
+        assert!(has_status);
+    }
+
+    #[test]
+    fn test_format_non_verbose_preserves_summary_records() {
+        // Test that summary and status records are still output when verbose=false
+        let formatter = JsonlFormatter::new();
+
+        // Create multiple rules with violations
+        let violations1 = vec![create_test_violation(
+            "no-unwrap",
+            "src/main.rs",
+            "src",
+            10,
+            5,
+            ".unwrap()",
+            "message",
+        )];
+        let violations2 = vec![
+            create_test_violation("no-todo", "src/lib.rs", "src", 20, 5, "// TODO", "message"),
+            create_test_violation("no-todo", "src/util.rs", "src", 30, 5, "// TODO", "message"),
+        ];
+
+        let status1 = create_test_status("no-unwrap", "src", 1, 5, violations1);
+        let status2 = create_test_status("no-todo", "src", 2, 1, violations2);
+
+        let result = AggregationResult {
+            statuses: vec![status1, status2],
+            passed: false,
+            total_violations: 3,
+            violations_over_budget: 1,
+        };
+
+        let output = formatter.format(&result, false);
+        let lines: Vec<&str> = output.lines().collect();
+
+        // Should have 3 lines: 2 summaries + 1 status (no violation records)
+        assert_eq!(lines.len(), 3);
+
+        // Verify first two lines are summaries
+        let summary1: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        let summary2: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(summary1["type"], "summary");
+        assert_eq!(summary2["type"], "summary");
+
+        // Verify last line is status
+        let status: serde_json::Value = serde_json::from_str(lines[2]).unwrap();
+        assert_eq!(status["type"], "status");
+        assert_eq!(status["passed"], false);
+        assert_eq!(status["rules_checked"], 2);
+        assert_eq!(status["rules_exceeded"], 1);
+        assert_eq!(status["total_violations"], 3);
+    }
+
+    #[test]
+    fn test_json_formatter_emits_one_document_with_all_three_sections() {
+        let formatter = JsonDocumentFormatter::new();
+        let violations = vec![create_test_violation(
+            "no-unwrap",
+            "src/main.rs",
+            "src",
+            10,
+            5,
+            ".unwrap()",
+            "Disallow .unwrap() calls",
+        )];
+        let status = create_test_status("no-unwrap", "src", 1, 5, violations);
+        let result = AggregationResult {
+            statuses: vec![status],
+            passed: true,
+            total_violations: 1,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result, true);
+        let doc: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+        assert_eq!(doc["violations"].as_array().unwrap().len(), 1);
+        assert_eq!(doc["violations"][0]["rule"], "no-unwrap");
+        assert_eq!(doc["summaries"].as_array().unwrap().len(), 1);
+        assert_eq!(doc["summaries"][0]["rule"], "no-unwrap");
+        assert_eq!(doc["status"]["passed"], true);
+        assert_eq!(doc["status"]["total_violations"], 1);
+    }
+
+    #[test]
+    fn test_json_formatter_without_verbose_omits_violations() {
+        let formatter = JsonDocumentFormatter::new();
+        let violations = vec![create_test_violation(
+            "no-unwrap",
+            "src/main.rs",
+            "src",
+            10,
+            5,
+            ".unwrap()",
+            "Disallow .unwrap() calls",
+        )];
+        let status = create_test_status("no-unwrap", "src", 1, 5, violations);
+        let result = AggregationResult {
+            statuses: vec![status],
+            passed: true,
+            total_violations: 1,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result, false);
+        let doc: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+        assert_eq!(doc["violations"].as_array().unwrap().len(), 0);
+        assert_eq!(doc["summaries"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_json_formatter_sorts_violations_by_rule_then_file_then_line() {
+        let formatter = JsonDocumentFormatter::new();
+        let status_b = create_test_status(
+            "no-unwrap",
+            "src",
+            2,
+            5,
+            vec![
+                create_test_violation("no-unwrap", "src/b.rs", "src", 5, 1, "s", "m"),
+                create_test_violation("no-unwrap", "src/a.rs", "src", 1, 1, "s", "m"),
+            ],
+        );
+        let status_a = create_test_status(
+            "no-todo",
+            "src",
+            1,
+            5,
+            vec![create_test_violation(
+                "no-todo", "src/a.rs", "src", 1, 1, "s", "m",
+            )],
+        );
+        let result = AggregationResult {
+            statuses: vec![status_b, status_a],
+            passed: true,
+            total_violations: 3,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result, true);
+        let doc: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let rules: Vec<&str> = doc["violations"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v["rule"].as_str().unwrap())
+            .collect();
+        assert_eq!(rules, vec!["no-todo", "no-unwrap", "no-unwrap"]);
+        let files: Vec<&str> = doc["violations"].as_array().unwrap()[1..]
+            .iter()
+            .map(|v| v["file"].as_str().unwrap())
+            .collect();
+        assert_eq!(files, vec!["src/a.rs", "src/b.rs"]);
+    }
+
+    #[test]
+    fn test_json_formatter_used_through_formatter_trait() {
+        let formatter: Box<dyn Formatter> = Box::new(JsonDocumentFormatter::new());
+        let result = AggregationResult {
+            statuses: vec![],
+            passed: true,
+            total_violations: 0,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result, true);
+        let doc: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(doc["status"]["passed"], true);
+    }
+
+    #[test]
+    fn test_json_rule_formatter_groups_violations_under_their_rule() {
+        let formatter = JsonFormatter::new();
+        let violations = vec![create_test_violation(
+            "no-unwrap",
+            "src/main.rs",
+            "src",
+            10,
+            5,
+            ".unwrap()",
+            "Disallow .unwrap() calls",
         )];
         let status = create_test_status("no-unwrap", "src", 1, 5, violations);
         let result = AggregationResult {
@@ -255,198 +3048,501 @@ mod tests {
         };
 
         let output = formatter.format(&result, true);
-        let lines: Vec<&str> = output.lines().collect();
-        assert_eq!(lines.len(), 3); // 1 violation + 1 summary + 1 status
-
-        // Verify violation record
-        let violation: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
-        assert_eq!(violation["type"], "violation");
-        assert_eq!(violation["rule"], "no-unwrap");
-        assert_eq!(violation["file"], "src/main.rs");
-        assert_eq!(violation["line"], 10);
-        assert_eq!(violation["column"], 5);
-        assert_eq!(violation["end_line"], 10);
-        assert_eq!(violation["end_column"], 15);
-        assert_eq!(violation["snippet"], ".unwrap()");
-        assert_eq!(violation["message"], "Disallow .unwrap() calls");
-        assert_eq!(violation["region"], "src");
+        let doc: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+        assert_eq!(doc["passed"], true);
+        assert_eq!(doc["total_violations"], 1);
+        assert_eq!(doc["violations_over_budget"], 0);
+        assert_eq!(doc["rules"].as_array().unwrap().len(), 1);
+        let rule = &doc["rules"][0];
+        assert_eq!(rule["rule_id"], "no-unwrap");
+        assert_eq!(rule["region"], "src");
+        assert_eq!(rule["actual_count"], 1);
+        assert_eq!(rule["budget"], 5);
+        assert_eq!(rule["passed"], true);
+        assert_eq!(rule["violations"].as_array().unwrap().len(), 1);
+        assert_eq!(rule["violations"][0]["file"], "src/main.rs");
+        assert_eq!(rule["violations"][0]["line"], 10);
+        assert_eq!(rule["violations"][0]["message"], "Disallow .unwrap() calls");
+    }
 
-        // Verify summary record
-        let summary: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
-        assert_eq!(summary["type"], "summary");
-        assert_eq!(summary["rule"], "no-unwrap");
-        assert_eq!(summary["region"], "src");
-        assert_eq!(summary["violations"], 1);
-        assert_eq!(summary["budget"], 5);
-        assert_eq!(summary["status"], "pass");
+    #[test]
+    fn test_json_rule_formatter_without_verbose_omits_nested_violations() {
+        let formatter = JsonFormatter::new();
+        let violations = vec![create_test_violation(
+            "no-unwrap",
+            "src/main.rs",
+            "src",
+            10,
+            5,
+            ".unwrap()",
+            "message",
+        )];
+        let status = create_test_status("no-unwrap", "src", 1, 5, violations);
+        let result = AggregationResult {
+            statuses: vec![status],
+            passed: true,
+            total_violations: 1,
+            violations_over_budget: 0,
+        };
 
-        // Verify status record
-        let status: serde_json::Value = serde_json::from_str(lines[2]).unwrap();
-        assert_eq!(status["type"], "status");
-        assert_eq!(status["passed"], true);
-        assert_eq!(status["rules_checked"], 1);
-        assert_eq!(status["rules_exceeded"], 0);
-        assert_eq!(status["total_violations"], 1);
+        let output = formatter.format(&result, false);
+        let doc: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(doc["rules"][0]["violations"].as_array().unwrap().len(), 0);
     }
 
     #[test]
-    fn test_format_multiple_violations_sorted() {
-        let formatter = JsonlFormatter::new();
+    fn test_json_rule_formatter_sorts_rules_by_rule_id_then_region() {
+        let formatter = JsonFormatter::new();
+        let status_b = create_test_status("no-unwrap", "src-b", 1, 5, vec![]);
+        let status_a1 = create_test_status("no-todo", "src-b", 1, 5, vec![]);
+        let status_a2 = create_test_status("no-todo", "src-a", 1, 5, vec![]);
+        let result = AggregationResult {
+            statuses: vec![status_b, status_a1, status_a2],
+            passed: true,
+            total_violations: 0,
+            violations_over_budget: 0,
+        };
 
-        // Create violations in unsorted order
-        let violations1 = vec![create_test_violation(
-            "rule-b", "src/z.rs", "src", 20, 5, "snippet2", "message2",
-        )];
-        let violations2 = vec![create_test_violation(
-            "rule-a", "src/a.rs", "src", 10, 5, "snippet1", "message1",
+        let output = formatter.format(&result, true);
+        let doc: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let ordering: Vec<(String, String)> = doc["rules"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|rule| {
+                (
+                    rule["rule_id"].as_str().unwrap().to_string(),
+                    rule["region"].as_str().unwrap().to_string(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            ordering,
+            vec![
+                ("no-todo".to_string(), "src-a".to_string()),
+                ("no-todo".to_string(), "src-b".to_string()),
+                ("no-unwrap".to_string(), "src-b".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_json_rule_formatter_write_streams_to_an_io_write() {
+        let formatter = JsonFormatter::new();
+        let violations = vec![create_test_violation(
+            "no-unwrap",
+            "src/main.rs",
+            "src",
+            10,
+            5,
+            ".unwrap()",
+            "message",
         )];
+        let status = create_test_status("no-unwrap", "src", 1, 5, violations);
+        let result = AggregationResult {
+            statuses: vec![status],
+            passed: true,
+            total_violations: 1,
+            violations_over_budget: 0,
+        };
 
-        let status1 = create_test_status("rule-b", "src", 1, 5, violations1);
-        let status2 = create_test_status("rule-a", "src", 1, 5, violations2);
+        let mut buffer = Vec::new();
+        formatter.write(&result, &mut buffer).unwrap();
+        let doc: serde_json::Value = serde_json::from_slice(&buffer).unwrap();
+        assert_eq!(doc["rules"][0]["violations"].as_array().unwrap().len(), 1);
+    }
 
+    #[test]
+    fn test_json_rule_formatter_used_through_formatter_trait() {
+        let formatter: Box<dyn Formatter> = Box::new(JsonFormatter::new());
         let result = AggregationResult {
-            statuses: vec![status1, status2],
+            statuses: vec![],
+            passed: true,
+            total_violations: 0,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result, true);
+        let doc: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(doc["passed"], true);
+        assert_eq!(doc["rules"].as_array().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_jsonl_formatter_used_through_formatter_trait_matches_inherent_method() {
+        let formatter: Box<dyn Formatter> = Box::new(JsonlFormatter::new());
+        let result = AggregationResult {
+            statuses: vec![],
+            passed: true,
+            total_violations: 0,
+            violations_over_budget: 0,
+        };
+
+        let via_trait = formatter.format(&result, true);
+        let via_inherent = JsonlFormatter::new().format(&result, true);
+        assert_eq!(via_trait, via_inherent);
+    }
+
+    #[test]
+    fn test_github_markdown_formatter_wraps_table_in_collapsible_details() {
+        let formatter = GitHubMarkdownFormatter::new();
+        let result = AggregationResult {
+            statuses: vec![
+                create_test_status("no-unwrap", "src/main.rs", 1, 5, vec![]),
+                create_test_status("no-console-log", "src/lib.rs", 10, 5, vec![]),
+            ],
+            passed: false,
+            total_violations: 11,
+            violations_over_budget: 1,
+        };
+
+        let output = formatter.format(&result, true);
+
+        assert!(output.starts_with("<details>\n"));
+        assert!(output.trim_end().ends_with("</details>"));
+        assert!(output.contains("<summary>Ratchet results: 1 passed, 1 failed</summary>"));
+        assert!(output.contains("| no-unwrap | src/main.rs | ✅ Pass | 1 | 5 |"));
+        assert!(output.contains("| no-console-log | src/lib.rs | ❌ Fail | 10 | 5 |"));
+    }
+
+    #[test]
+    fn test_github_markdown_formatter_sorts_rows_by_severity_then_rule_then_region() {
+        let formatter = GitHubMarkdownFormatter::new();
+        let mut warning_status = create_test_status("b-rule", "region", 1, 5, vec![]);
+        warning_status.severity = Severity::Warning;
+        let result = AggregationResult {
+            statuses: vec![
+                warning_status,
+                create_test_status("a-rule", "region", 1, 5, vec![]),
+            ],
             passed: true,
             total_violations: 2,
             violations_over_budget: 0,
         };
 
         let output = formatter.format(&result, true);
-        let lines: Vec<&str> = output.lines().collect();
 
-        // Verify violations are sorted by rule, then file, then line
-        let v1: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
-        let v2: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
+        let a_rule_pos = output.find("a-rule").unwrap();
+        let b_rule_pos = output.find("b-rule").unwrap();
+        assert!(a_rule_pos < b_rule_pos);
+    }
 
-        assert_eq!(v1["rule"], "rule-a");
-        assert_eq!(v2["rule"], "rule-b");
+    #[test]
+    fn test_ratio_formatter_emits_one_line_per_rule() {
+        let formatter = RatioFormatter::new();
+        let result = AggregationResult {
+            statuses: vec![
+                create_test_status("no-unwrap", "src/main.rs", 1, 5, vec![]),
+                create_test_status("no-console-log", "src/lib.rs", 10, 5, vec![]),
+            ],
+            passed: false,
+            total_violations: 11,
+            violations_over_budget: 1,
+        };
 
-        // Verify summaries are sorted by rule, then region
-        let s1: serde_json::Value = serde_json::from_str(lines[2]).unwrap();
-        let s2: serde_json::Value = serde_json::from_str(lines[3]).unwrap();
+        let output = formatter.format(&result, true);
 
-        assert_eq!(s1["rule"], "rule-a");
-        assert_eq!(s2["rule"], "rule-b");
+        assert_eq!(
+            output,
+            "no-console-log src/lib.rs: 10/5\nno-unwrap src/main.rs: 1/5"
+        );
     }
 
     #[test]
-    fn test_format_violation_over_budget() {
-        let formatter = JsonlFormatter::new();
-        let violations = vec![
-            create_test_violation(
+    fn test_ratio_formatter_empty_result_is_empty_string() {
+        let formatter = RatioFormatter::new();
+        let result = AggregationResult {
+            statuses: vec![],
+            passed: true,
+            total_violations: 0,
+            violations_over_budget: 0,
+        };
+
+        assert_eq!(formatter.format(&result, true), "");
+    }
+
+    #[test]
+    fn test_github_actions_formatter_emits_error_for_over_budget_rule() {
+        let formatter = GitHubActionsFormatter::new();
+        let violation = create_test_violation(
+            "no-unwrap",
+            "src/main.rs",
+            "global",
+            10,
+            5,
+            ".unwrap()",
+            "avoid unwrap",
+        );
+        let result = AggregationResult {
+            statuses: vec![create_test_status(
+                "no-unwrap",
+                "global",
+                6,
+                5,
+                vec![violation],
+            )],
+            passed: false,
+            total_violations: 1,
+            violations_over_budget: 1,
+        };
+
+        let output = formatter.format(&result, true);
+
+        assert_eq!(
+            output,
+            "::error file=src/main.rs,line=10,col=5::no-unwrap: .unwrap()"
+        );
+    }
+
+    #[test]
+    fn test_github_actions_formatter_emits_warning_for_rule_with_remaining_budget() {
+        let formatter = GitHubActionsFormatter::new();
+        let violation = create_test_violation(
+            "no-unwrap",
+            "src/main.rs",
+            "global",
+            10,
+            5,
+            ".unwrap()",
+            "avoid unwrap",
+        );
+        let result = AggregationResult {
+            statuses: vec![create_test_status(
+                "no-unwrap",
+                "global",
+                1,
+                5,
+                vec![violation],
+            )],
+            passed: true,
+            total_violations: 1,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result, true);
+
+        assert_eq!(
+            output,
+            "::warning file=src/main.rs,line=10,col=5::no-unwrap: .unwrap()"
+        );
+    }
+
+    #[test]
+    fn test_github_actions_formatter_with_summary_appends_notice_line() {
+        let formatter = GitHubActionsFormatter::new().with_summary();
+        let result = AggregationResult {
+            statuses: vec![],
+            passed: false,
+            total_violations: 0,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result, true);
+
+        assert_eq!(output, "::notice::Check FAILED");
+    }
+
+    #[test]
+    fn test_github_actions_formatter_without_summary_omits_notice_line() {
+        let formatter = GitHubActionsFormatter::new();
+        let result = AggregationResult {
+            statuses: vec![],
+            passed: true,
+            total_violations: 0,
+            violations_over_budget: 0,
+        };
+
+        assert_eq!(formatter.format(&result, true), "");
+    }
+
+    #[test]
+    fn test_escape_property_escapes_percent_cr_lf_comma_and_colon() {
+        assert_eq!(escape_property("a%b\rc\nd,e:f"), "a%25b%0Dc%0Ad%2Ce%3Af");
+    }
+
+    #[test]
+    fn test_escape_message_leaves_comma_and_colon_untouched() {
+        assert_eq!(escape_message("rule: a, b\n%"), "rule: a, b%0A%25");
+    }
+
+    #[test]
+    fn test_sarif_format_empty_result() {
+        let formatter = SarifFormatter::new();
+        let result = AggregationResult {
+            statuses: vec![],
+            passed: true,
+            total_violations: 0,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result);
+        let log: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+        assert_eq!(log["version"], "2.1.0");
+        let run = &log["runs"][0];
+        assert_eq!(run["tool"]["driver"]["rules"].as_array().unwrap().len(), 0);
+        assert_eq!(run["results"].as_array().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_sarif_format_sets_level_from_passed_status() {
+        let formatter = SarifFormatter::new();
+
+        let passing = create_test_status(
+            "no-unwrap",
+            "src",
+            1,
+            5,
+            vec![create_test_violation(
                 "no-unwrap",
                 "src/main.rs",
                 "src",
                 10,
                 5,
                 ".unwrap()",
-                "Disallow .unwrap() calls",
-            ),
-            create_test_violation(
-                "no-unwrap",
+                "message",
+            )],
+        );
+        let failing = create_test_status(
+            "no-todo",
+            "src",
+            2,
+            1,
+            vec![create_test_violation(
+                "no-todo",
                 "src/lib.rs",
                 "src",
                 20,
                 5,
-                "result.unwrap()",
-                "Disallow .unwrap() calls",
-            ),
-        ];
-        let status = create_test_status("no-unwrap", "src", 2, 1, violations);
+                "// TODO",
+                "message",
+            )],
+        );
+
         let result = AggregationResult {
-            statuses: vec![status],
+            statuses: vec![passing, failing],
             passed: false,
             total_violations: 2,
             violations_over_budget: 1,
         };
 
-        let output = formatter.format(&result, true);
-        let lines: Vec<&str> = output.lines().collect();
-        assert_eq!(lines.len(), 4); // 2 violations + 1 summary + 1 status
-
-        // Verify summary shows fail status
-        let summary: serde_json::Value = serde_json::from_str(lines[2]).unwrap();
-        assert_eq!(summary["status"], "fail");
-        assert_eq!(summary["violations"], 2);
-        assert_eq!(summary["budget"], 1);
+        let output = formatter.format(&result);
+        let log: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let results = log["runs"][0]["results"].as_array().unwrap();
 
-        // Verify status record shows failure
-        let status: serde_json::Value = serde_json::from_str(lines[3]).unwrap();
-        assert_eq!(status["passed"], false);
-        assert_eq!(status["rules_exceeded"], 1);
+        // Sorted by rule first: "no-todo" (over budget) before "no-unwrap" (within budget)
+        assert_eq!(results[0]["ruleId"], "no-todo");
+        assert_eq!(results[0]["level"], "error");
+        assert_eq!(results[1]["ruleId"], "no-unwrap");
+        assert_eq!(results[1]["level"], "warning");
     }
 
     #[test]
-    fn test_format_multiple_rules_and_regions() {
-        let formatter = JsonlFormatter::new();
-
-        let violations1 = vec![create_test_violation(
+    fn test_sarif_format_maps_violation_location_fields() {
+        let formatter = SarifFormatter::new();
+        let violations = vec![create_test_violation(
             "no-unwrap",
             "src/main.rs",
             "src",
             10,
             5,
             ".unwrap()",
-            "message",
+            "Disallow .unwrap() calls",
         )];
-        let violations2 = vec![create_test_violation(
+        let status = create_test_status("no-unwrap", "src", 1, 5, violations);
+        let result = AggregationResult {
+            statuses: vec![status],
+            passed: true,
+            total_violations: 1,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format(&result);
+        let log: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let sarif_result = &log["runs"][0]["results"][0];
+
+        assert_eq!(sarif_result["ruleId"], "no-unwrap");
+        assert_eq!(sarif_result["message"]["text"], "Disallow .unwrap() calls");
+
+        let location = &sarif_result["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "src/main.rs");
+        assert_eq!(location["region"]["startLine"], 10);
+        assert_eq!(location["region"]["startColumn"], 5);
+        assert_eq!(location["region"]["endLine"], 10);
+        assert_eq!(location["region"]["endColumn"], 15);
+        assert_eq!(location["region"]["snippet"]["text"], ".unwrap()");
+    }
+
+    #[test]
+    fn test_sarif_format_driver_rules_are_distinct_and_sorted() {
+        let formatter = SarifFormatter::new();
+
+        let status1 = create_test_status(
             "no-unwrap",
-            "tests/test.rs",
-            "tests",
-            20,
+            "src",
+            2,
             5,
-            ".unwrap()",
-            "message",
-        )];
-        let violations3 = vec![create_test_violation(
+            vec![
+                create_test_violation("no-unwrap", "src/a.rs", "src", 1, 1, "s1", "m1"),
+                create_test_violation("no-unwrap", "src/b.rs", "src", 2, 1, "s2", "m2"),
+            ],
+        );
+        let status2 = create_test_status(
             "no-todo",
-            "src/lib.rs",
-            "src",
-            30,
+            "tests",
+            1,
             5,
-            "// TODO",
-            "message",
-        )];
-
-        let status1 = create_test_status("no-unwrap", "src", 1, 5, violations1);
-        let status2 = create_test_status("no-unwrap", "tests", 1, 10, violations2);
-        let status3 = create_test_status("no-todo", "src", 1, 3, violations3);
+            vec![create_test_violation(
+                "no-todo",
+                "tests/t.rs",
+                "tests",
+                3,
+                1,
+                "s3",
+                "m3",
+            )],
+        );
 
         let result = AggregationResult {
-            statuses: vec![status1, status2, status3],
+            statuses: vec![status1, status2],
             passed: true,
             total_violations: 3,
             violations_over_budget: 0,
         };
 
-        let output = formatter.format(&result, true);
-        let lines: Vec<&str> = output.lines().collect();
-        assert_eq!(lines.len(), 7); // 3 violations + 3 summaries + 1 status
-
-        // Verify violations are properly sorted
-        let v1: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
-        let v2: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
-        let v3: serde_json::Value = serde_json::from_str(lines[2]).unwrap();
+        let output = formatter.format(&result);
+        let log: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let rules = log["runs"][0]["tool"]["driver"]["rules"]
+            .as_array()
+            .unwrap();
 
-        assert_eq!(v1["rule"], "no-todo");
-        assert_eq!(v2["rule"], "no-unwrap");
-        assert_eq!(v3["rule"], "no-unwrap");
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0]["id"], "no-todo");
+        assert_eq!(rules[1]["id"], "no-unwrap");
+    }
 
-        // Verify summaries are properly sorted
-        let s1: serde_json::Value = serde_json::from_str(lines[3]).unwrap();
-        let s2: serde_json::Value = serde_json::from_str(lines[4]).unwrap();
-        let s3: serde_json::Value = serde_json::from_str(lines[5]).unwrap();
+    #[test]
+    fn test_sarif_fingerprint_is_stable_and_distinguishes_violations() {
+        let a = sarif_fingerprint(
+            "no-unwrap",
+            std::path::Path::new("src/main.rs"),
+            ".unwrap()",
+        );
+        let b = sarif_fingerprint(
+            "no-unwrap",
+            std::path::Path::new("src/main.rs"),
+            ".unwrap()",
+        );
+        let c = sarif_fingerprint("no-unwrap", std::path::Path::new("src/lib.rs"), ".unwrap()");
 
-        assert_eq!(s1["rule"], "no-todo");
-        assert_eq!(s2["rule"], "no-unwrap");
-        assert_eq!(s2["region"], "src");
-        assert_eq!(s3["rule"], "no-unwrap");
-        assert_eq!(s3["region"], "tests");
+        assert_eq!(a, b);
+        assert_ne!(a, c);
     }
 
     #[test]
-    fn test_json_validity() {
-        let formatter = JsonlFormatter::new();
+    fn test_sarif_format_is_valid_json() {
+        let formatter = SarifFormatter::new();
         let violations = vec![create_test_violation(
             "test-rule",
             "src/test.rs",
@@ -464,18 +3560,14 @@ mod tests {
             violations_over_budget: 0,
         };
 
-        let output = formatter.format(&result, true);
-
-        // Verify each line is valid JSON
-        for line in output.lines() {
-            let parsed: Result<serde_json::Value, _> = serde_json::from_str(line);
-            assert!(parsed.is_ok(), "Invalid JSON: {}", line);
-        }
+        let output = formatter.format(&result);
+        let parsed: Result<serde_json::Value, _> = serde_json::from_str(&output);
+        assert!(parsed.is_ok(), "Invalid JSON: {}", output);
     }
 
     #[test]
-    fn test_default_implementation() {
-        let formatter = JsonlFormatter;
+    fn test_sarif_default_implementation() {
+        let formatter = SarifFormatter;
         let result = AggregationResult {
             statuses: vec![],
             passed: true,
@@ -483,565 +3575,837 @@ mod tests {
             violations_over_budget: 0,
         };
 
-        let output = formatter.format(&result, true);
+        let output = formatter.format(&result);
         assert!(!output.is_empty());
     }
 
     #[test]
-    fn test_violation_sorting_by_line() {
+    fn test_parse_round_trips_formatted_output() {
         let formatter = JsonlFormatter::new();
-
-        // Create violations with same rule and file but different lines
-        let violations = vec![
-            create_test_violation("rule-a", "src/file.rs", "src", 30, 5, "s3", "m3"),
-            create_test_violation("rule-a", "src/file.rs", "src", 10, 5, "s1", "m1"),
-            create_test_violation("rule-a", "src/file.rs", "src", 20, 5, "s2", "m2"),
-        ];
-
-        let status = create_test_status("rule-a", "src", 3, 5, violations);
+        let violations = vec![create_test_violation(
+            "no-unwrap",
+            "src/main.rs",
+            "src",
+            10,
+            5,
+            ".unwrap()",
+            "message",
+        )];
+        let status = create_test_status("no-unwrap", "src", 1, 5, violations);
         let result = AggregationResult {
             statuses: vec![status],
             passed: true,
-            total_violations: 3,
+            total_violations: 1,
             violations_over_budget: 0,
         };
 
         let output = formatter.format(&result, true);
-        let lines: Vec<&str> = output.lines().collect();
+        let records = parse(&output);
 
-        // Verify violations are sorted by line number
-        let v1: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
-        let v2: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
-        let v3: serde_json::Value = serde_json::from_str(lines[2]).unwrap();
+        assert_eq!(records.len(), 3);
+        assert!(matches!(records[0], Record::Violation(_)));
+        assert!(matches!(records[1], Record::Summary(_)));
+        assert!(matches!(records[2], Record::Status(_)));
+    }
 
-        assert_eq!(v1["line"], 10);
-        assert_eq!(v2["line"], 20);
-        assert_eq!(v3["line"], 30);
+    #[test]
+    fn test_parse_ignores_unknown_record_types_and_invalid_lines() {
+        let input = "{\"type\": \"future-record\", \"foo\": \"bar\"}\nnot json at all\n{\"type\": \"status\", \"passed\": true, \"rules_checked\": 0, \"rules_exceeded\": 0, \"errors_exceeded\": 0, \"warnings_exceeded\": 0, \"total_violations\": 0, \"exit_code\": 0, \"source\": \"\"}";
+
+        let records = parse(input);
+
+        assert_eq!(records.len(), 1);
+        assert!(matches!(records[0], Record::Status(_)));
     }
 
     #[test]
-    fn test_special_characters_in_paths() {
+    fn test_format_diff_classifies_added_removed_and_unchanged() {
         let formatter = JsonlFormatter::new();
 
-        // Test with spaces, unicode, and special characters in paths
-        let violations = [
-            create_test_violation(
-                "no-unwrap",
-                "src/my file.rs",
-                "src",
-                10,
-                5,
-                ".unwrap()",
-                "message",
-            ),
-            create_test_violation(
-                "no-todo",
-                "src/日本語.rs",
-                "src",
-                20,
-                5,
-                "// TODO",
-                "message",
-            ),
-            create_test_violation(
-                "no-panic",
-                "src/file's.rs",
-                "src",
-                30,
-                5,
-                "panic!",
-                "message",
-            ),
+        let baseline = vec![
+            Record::Violation(ViolationRecord {
+                record_type: "violation".to_string(),
+                rule: "no-unwrap".to_string(),
+                file: PathBuf::from("src/main.rs"),
+                line: 1,
+                column: 1,
+                end_line: 1,
+                end_column: 10,
+                snippet: "kept".to_string(),
+                message: "message".to_string(),
+                region: "src".to_string(),
+                source: String::new(),
+                truncated: false,
+                snippet_bytes: 4,
+            }),
+            Record::Violation(ViolationRecord {
+                record_type: "violation".to_string(),
+                rule: "no-unwrap".to_string(),
+                file: PathBuf::from("src/old.rs"),
+                line: 2,
+                column: 1,
+                end_line: 2,
+                end_column: 10,
+                snippet: "gone".to_string(),
+                message: "message".to_string(),
+                region: "src".to_string(),
+                source: String::new(),
+                truncated: false,
+                snippet_bytes: 4,
+            }),
         ];
 
-        let status1 = create_test_status("no-unwrap", "src", 1, 5, vec![violations[0].clone()]);
-        let status2 = create_test_status("no-todo", "src", 1, 5, vec![violations[1].clone()]);
-        let status3 = create_test_status("no-panic", "src", 1, 5, vec![violations[2].clone()]);
-
-        let result = AggregationResult {
-            statuses: vec![status1, status2, status3],
+        let current_violations = vec![
+            // Same (rule, file, snippet) as a baseline entry, but the line
+            // moved — should still count as unchanged.
+            create_test_violation("no-unwrap", "src/main.rs", "src", 99, 1, "kept", "message"),
+            create_test_violation("no-unwrap", "src/new.rs", "src", 3, 1, "fresh", "message"),
+        ];
+        let status = create_test_status("no-unwrap", "src", 2, 5, current_violations);
+        let current = AggregationResult {
+            statuses: vec![status],
             passed: true,
-            total_violations: 3,
+            total_violations: 2,
             violations_over_budget: 0,
         };
 
-        let output = formatter.format(&result, true);
-
-        // Verify all lines are valid JSON
-        for line in output.lines() {
-            let parsed: Result<serde_json::Value, _> = serde_json::from_str(line);
-            assert!(parsed.is_ok(), "Invalid JSON: {}", line);
-        }
-
-        // Verify special characters in paths are properly JSON-encoded
-        // Violations are sorted by rule, then file, then line
+        let output = formatter.format_diff(&current, &baseline);
         let lines: Vec<&str> = output.lines().collect();
-        let v1: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
-        let v2: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
-        let v3: serde_json::Value = serde_json::from_str(lines[2]).unwrap();
-
-        // Sorted order: no-panic, no-todo, no-unwrap
-        assert_eq!(v1["rule"], "no-panic");
-        assert_eq!(v1["file"], "src/file's.rs");
-        assert_eq!(v2["rule"], "no-todo");
-        assert_eq!(v2["file"], "src/日本語.rs");
-        assert_eq!(v3["rule"], "no-unwrap");
-        assert_eq!(v3["file"], "src/my file.rs");
+        let violation_lines: Vec<serde_json::Value> = lines
+            .iter()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .filter(|v: &serde_json::Value| v["type"] == "violation")
+            .collect();
+
+        assert_eq!(violation_lines.len(), 3);
+
+        let find_change = |snippet: &str| {
+            violation_lines
+                .iter()
+                .find(|v| v["snippet"] == snippet)
+                .map(|v| v["change"].as_str().unwrap().to_string())
+                .unwrap()
+        };
+        assert_eq!(find_change("kept"), "unchanged");
+        assert_eq!(find_change("gone"), "removed");
+        assert_eq!(find_change("fresh"), "added");
     }
 
     #[test]
-    fn test_special_characters_in_snippets() {
+    fn test_format_diff_summary_reports_baseline_violations_and_delta() {
         let formatter = JsonlFormatter::new();
 
-        // Test with various special characters in snippets
-        let violations = vec![
-            create_test_violation(
-                "test",
-                "src/test.rs",
-                "src",
-                1,
-                1,
-                "\"hello\\nworld\"",
-                "newline in snippet",
-            ),
-            create_test_violation(
-                "test",
-                "src/test.rs",
-                "src",
-                2,
-                1,
-                "emoji: 🦀",
-                "emoji in snippet",
-            ),
-            create_test_violation(
-                "test",
-                "src/test.rs",
-                "src",
-                3,
-                1,
-                "{\"key\": \"value\"}",
-                "json in snippet",
-            ),
-            create_test_violation(
-                "test",
-                "src/test.rs",
-                "src",
-                4,
-                1,
-                "tab:\there",
-                "tab character",
-            ),
-        ];
-
-        let status = create_test_status("test", "src", 4, 10, violations);
-        let result = AggregationResult {
+        let baseline = vec![Record::Summary(SummaryRecord {
+            record_type: "summary".to_string(),
+            rule: "no-unwrap".to_string(),
+            region: "src".to_string(),
+            violations: 5,
+            budget: 10,
+            status: "pass".to_string(),
+            severity: "error".to_string(),
+            source: String::new(),
+        })];
+
+        let violations = vec![create_test_violation(
+            "no-unwrap",
+            "src/a.rs",
+            "src",
+            1,
+            1,
+            "s1",
+            "m1",
+        )];
+        let status = create_test_status("no-unwrap", "src", 1, 10, violations);
+        let current = AggregationResult {
             statuses: vec![status],
             passed: true,
-            total_violations: 4,
+            total_violations: 1,
             violations_over_budget: 0,
         };
 
-        let output = formatter.format(&result, true);
+        let output = formatter.format_diff(&current, &baseline);
+        let summary_line = output
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .find(|v| v["type"] == "summary")
+            .unwrap();
 
-        // Verify all lines are valid JSON
-        for line in output.lines() {
-            let parsed: Result<serde_json::Value, _> = serde_json::from_str(line);
-            assert!(parsed.is_ok(), "Invalid JSON: {}", line);
-        }
+        assert_eq!(summary_line["violations"], 1);
+        assert_eq!(summary_line["baseline_violations"], 5);
+        assert_eq!(summary_line["delta"], -4);
+    }
 
-        // Verify special characters are properly escaped
-        let lines: Vec<&str> = output.lines().collect();
-        let v1: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
-        let v2: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
-        let v3: serde_json::Value = serde_json::from_str(lines[2]).unwrap();
-        let v4: serde_json::Value = serde_json::from_str(lines[3]).unwrap();
+    #[test]
+    fn test_format_diff_summary_with_no_baseline_entry_reports_zero_baseline() {
+        let formatter = JsonlFormatter::new();
 
-        assert_eq!(v1["snippet"], "\"hello\\nworld\"");
-        assert_eq!(v2["snippet"], "emoji: 🦀");
-        assert_eq!(v3["snippet"], "{\"key\": \"value\"}");
-        assert_eq!(v4["snippet"], "tab:\there");
+        let violations = vec![create_test_violation(
+            "no-todo", "src/a.rs", "src", 1, 1, "s1", "m1",
+        )];
+        let status = create_test_status("no-todo", "src", 1, 10, violations);
+        let current = AggregationResult {
+            statuses: vec![status],
+            passed: true,
+            total_violations: 1,
+            violations_over_budget: 0,
+        };
+
+        let output = formatter.format_diff(&current, &[]);
+        let summary_line = output
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .find(|v| v["type"] == "summary")
+            .unwrap();
+
+        assert_eq!(summary_line["baseline_violations"], 0);
+        assert_eq!(summary_line["delta"], 1);
     }
 
     #[test]
-    fn test_deterministic_output() {
+    fn test_format_to_matches_format() {
         let formatter = JsonlFormatter::new();
-
-        // Create a complex result with multiple violations
-        let violations1 = [
+        let violations = vec![
             create_test_violation("rule-b", "src/z.rs", "src", 20, 5, "snippet2", "message2"),
             create_test_violation("rule-a", "src/a.rs", "src", 10, 5, "snippet1", "message1"),
         ];
-        let violations2 = vec![create_test_violation(
-            "rule-c",
-            "tests/test.rs",
-            "tests",
-            30,
+        let status = create_test_status("rule-b", "src", 2, 5, violations);
+        let result = AggregationResult {
+            statuses: vec![status],
+            passed: true,
+            total_violations: 2,
+            violations_over_budget: 0,
+        };
+
+        let expected = formatter.format(&result, true);
+
+        let mut buffer = Vec::new();
+        formatter.format_to(&result, true, &mut buffer).unwrap();
+        let actual = String::from_utf8(buffer).unwrap();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_format_to_non_verbose_omits_violation_records() {
+        let formatter = JsonlFormatter::new();
+        let violations = vec![create_test_violation(
+            "no-unwrap",
+            "src/main.rs",
+            "src",
+            10,
             5,
-            "snippet3",
-            "message3",
+            ".unwrap()",
+            "message",
         )];
+        let status = create_test_status("no-unwrap", "src", 1, 5, violations);
+        let result = AggregationResult {
+            statuses: vec![status],
+            passed: true,
+            total_violations: 1,
+            violations_over_budget: 0,
+        };
 
-        let status1 = create_test_status("rule-b", "src", 1, 5, vec![violations1[0].clone()]);
-        let status2 = create_test_status("rule-a", "src", 1, 5, vec![violations1[1].clone()]);
-        let status3 = create_test_status("rule-c", "tests", 1, 5, violations2);
+        let mut buffer = Vec::new();
+        formatter.format_to(&result, false, &mut buffer).unwrap();
+        let output = String::from_utf8(buffer).unwrap();
 
-        let result = AggregationResult {
-            statuses: vec![status1, status2, status3],
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 2); // 1 summary + 1 status
+    }
+
+    #[test]
+    fn test_merge_sums_actual_count_and_concatenates_violations() {
+        let shard1 = AggregationResult {
+            statuses: vec![create_test_status(
+                "no-unwrap",
+                "src",
+                1,
+                5,
+                vec![create_test_violation(
+                    "no-unwrap",
+                    "src/a.rs",
+                    "src",
+                    1,
+                    1,
+                    "s1",
+                    "m1",
+                )],
+            )],
+            passed: true,
+            total_violations: 1,
+            violations_over_budget: 0,
+        };
+        let shard2 = AggregationResult {
+            statuses: vec![create_test_status(
+                "no-unwrap",
+                "src",
+                1,
+                5,
+                vec![create_test_violation(
+                    "no-unwrap",
+                    "src/b.rs",
+                    "src",
+                    2,
+                    1,
+                    "s2",
+                    "m2",
+                )],
+            )],
+            passed: true,
+            total_violations: 1,
+            violations_over_budget: 0,
+        };
+
+        let merged = merge(&[shard1, shard2]).unwrap();
+
+        assert_eq!(merged.statuses.len(), 1);
+        let status = &merged.statuses[0];
+        assert_eq!(status.actual_count, 2);
+        assert_eq!(status.raw_count, 2);
+        assert_eq!(status.violations.len(), 2);
+        assert_eq!(status.budget, 5);
+        assert!(status.passed);
+    }
+
+    #[test]
+    fn test_merge_recomputes_passed_against_shared_budget() {
+        let shard1 = AggregationResult {
+            statuses: vec![create_test_status(
+                "no-unwrap",
+                "src",
+                3,
+                5,
+                vec![
+                    create_test_violation("no-unwrap", "src/a.rs", "src", 1, 1, "s1", "m1"),
+                    create_test_violation("no-unwrap", "src/b.rs", "src", 2, 1, "s2", "m2"),
+                    create_test_violation("no-unwrap", "src/c.rs", "src", 3, 1, "s3", "m3"),
+                ],
+            )],
+            passed: true,
+            total_violations: 3,
+            violations_over_budget: 0,
+        };
+        let shard2 = AggregationResult {
+            statuses: vec![create_test_status(
+                "no-unwrap",
+                "src",
+                3,
+                5,
+                vec![
+                    create_test_violation("no-unwrap", "src/d.rs", "src", 4, 1, "s4", "m4"),
+                    create_test_violation("no-unwrap", "src/e.rs", "src", 5, 1, "s5", "m5"),
+                    create_test_violation("no-unwrap", "src/f.rs", "src", 6, 1, "s6", "m6"),
+                ],
+            )],
             passed: true,
             total_violations: 3,
             violations_over_budget: 0,
         };
 
-        // Format the same result multiple times
-        let output1 = formatter.format(&result, true);
-        let output2 = formatter.format(&result, true);
-        let output3 = formatter.format(&result, true);
+        let merged = merge(&[shard1, shard2]).unwrap();
 
-        // All outputs should be byte-for-byte identical
-        assert_eq!(output1, output2);
-        assert_eq!(output2, output3);
+        // Each shard passed on its own (3 <= 5), but combined (6 > 5) fails.
+        let status = &merged.statuses[0];
+        assert_eq!(status.actual_count, 6);
+        assert!(!status.passed);
+        assert_eq!(status.over_budget, 1);
+        assert!(!merged.passed);
+        assert_eq!(merged.violations_over_budget, 1);
+        assert_eq!(merged.total_violations, 6);
+    }
 
-        // Verify the output is sorted correctly
-        let lines: Vec<&str> = output1.lines().collect();
+    #[test]
+    fn test_merge_conflicting_budgets_returns_error() {
+        let shard1 = AggregationResult {
+            statuses: vec![create_test_status("no-unwrap", "src", 1, 5, vec![])],
+            passed: true,
+            total_violations: 0,
+            violations_over_budget: 0,
+        };
+        let shard2 = AggregationResult {
+            statuses: vec![create_test_status("no-unwrap", "src", 1, 10, vec![])],
+            passed: true,
+            total_violations: 0,
+            violations_over_budget: 0,
+        };
 
-        // First 3 lines should be violations sorted by rule, file, line
-        let v1: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
-        let v2: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
-        let v3: serde_json::Value = serde_json::from_str(lines[2]).unwrap();
+        let result = merge(&[shard1, shard2]);
 
-        assert_eq!(v1["rule"], "rule-a");
-        assert_eq!(v2["rule"], "rule-b");
-        assert_eq!(v3["rule"], "rule-c");
+        assert!(matches!(
+            result,
+            Err(MergeError::BudgetConflict {
+                first: 5,
+                second: 10,
+                ..
+            })
+        ));
+    }
 
-        // Next 3 lines should be summaries sorted by rule, region
-        let s1: serde_json::Value = serde_json::from_str(lines[3]).unwrap();
-        let s2: serde_json::Value = serde_json::from_str(lines[4]).unwrap();
-        let s3: serde_json::Value = serde_json::from_str(lines[5]).unwrap();
+    #[test]
+    fn test_merge_independent_keys_stay_separate() {
+        let shard1 = AggregationResult {
+            statuses: vec![create_test_status(
+                "no-unwrap",
+                "src",
+                1,
+                5,
+                vec![create_test_violation(
+                    "no-unwrap",
+                    "src/a.rs",
+                    "src",
+                    1,
+                    1,
+                    "s1",
+                    "m1",
+                )],
+            )],
+            passed: true,
+            total_violations: 1,
+            violations_over_budget: 0,
+        };
+        let shard2 = AggregationResult {
+            statuses: vec![create_test_status(
+                "no-todo",
+                "tests",
+                1,
+                5,
+                vec![create_test_violation(
+                    "no-todo",
+                    "tests/b.rs",
+                    "tests",
+                    1,
+                    1,
+                    "s2",
+                    "m2",
+                )],
+            )],
+            passed: true,
+            total_violations: 1,
+            violations_over_budget: 0,
+        };
 
-        assert_eq!(s1["type"], "summary");
-        assert_eq!(s1["rule"], "rule-a");
-        assert_eq!(s2["type"], "summary");
-        assert_eq!(s2["rule"], "rule-b");
-        assert_eq!(s3["type"], "summary");
-        assert_eq!(s3["rule"], "rule-c");
+        let merged = merge(&[shard1, shard2]).unwrap();
 
-        // Last line should be status
-        let status: serde_json::Value = serde_json::from_str(lines[6]).unwrap();
-        assert_eq!(status["type"], "status");
+        assert_eq!(merged.statuses.len(), 2);
+        assert_eq!(merged.statuses[0].rule_id.as_str(), "no-todo");
+        assert_eq!(merged.statuses[1].rule_id.as_str(), "no-unwrap");
     }
 
     #[test]
-    fn test_empty_and_whitespace_snippets() {
+    fn test_format_combined_formats_the_merged_result() {
         let formatter = JsonlFormatter::new();
-
-        let violations = vec![
-            create_test_violation("test", "src/test.rs", "src", 1, 1, "", "empty"),
-            create_test_violation("test", "src/test.rs", "src", 2, 1, "   ", "whitespace only"),
-            create_test_violation(
-                "test",
-                "src/test.rs",
+        let shard1 = AggregationResult {
+            statuses: vec![create_test_status(
+                "no-unwrap",
                 "src",
-                3,
                 1,
-                "\n\n\n",
-                "newlines only",
-            ),
-        ];
-
-        let status = create_test_status("test", "src", 3, 10, violations);
-        let result = AggregationResult {
-            statuses: vec![status],
+                5,
+                vec![create_test_violation(
+                    "no-unwrap",
+                    "src/a.rs",
+                    "src",
+                    1,
+                    1,
+                    "s1",
+                    "m1",
+                )],
+            )],
             passed: true,
-            total_violations: 3,
+            total_violations: 1,
+            violations_over_budget: 0,
+        };
+        let shard2 = AggregationResult {
+            statuses: vec![create_test_status(
+                "no-unwrap",
+                "src",
+                1,
+                5,
+                vec![create_test_violation(
+                    "no-unwrap",
+                    "src/b.rs",
+                    "src",
+                    2,
+                    1,
+                    "s2",
+                    "m2",
+                )],
+            )],
+            passed: true,
+            total_violations: 1,
             violations_over_budget: 0,
         };
 
-        let output = formatter.format(&result, true);
+        let output = formatter.format_combined(&[shard1, shard2], false).unwrap();
+        let summary_line = output
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .find(|v| v["type"] == "summary")
+            .unwrap();
 
-        // Verify all lines are valid JSON
-        for line in output.lines() {
-            let parsed: Result<serde_json::Value, _> = serde_json::from_str(line);
-            assert!(parsed.is_ok(), "Invalid JSON: {}", line);
-        }
+        assert_eq!(summary_line["violations"], 2);
+    }
 
-        // Verify snippets are preserved as-is
-        let lines: Vec<&str> = output.lines().collect();
-        let v1: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
-        let v2: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
-        let v3: serde_json::Value = serde_json::from_str(lines[2]).unwrap();
+    #[test]
+    fn test_format_combined_propagates_merge_error() {
+        let formatter = JsonlFormatter::new();
+        let shard1 = AggregationResult {
+            statuses: vec![create_test_status("no-unwrap", "src", 1, 5, vec![])],
+            passed: true,
+            total_violations: 0,
+            violations_over_budget: 0,
+        };
+        let shard2 = AggregationResult {
+            statuses: vec![create_test_status("no-unwrap", "src", 1, 10, vec![])],
+            passed: true,
+            total_violations: 0,
+            violations_over_budget: 0,
+        };
 
-        assert_eq!(v1["snippet"], "");
-        assert_eq!(v2["snippet"], "   ");
-        assert_eq!(v3["snippet"], "\n\n\n");
+        let result = formatter.format_combined(&[shard1, shard2], false);
+        assert!(result.is_err());
     }
 
     #[test]
-    fn test_long_snippet_json_encoding() {
+    fn test_format_delta_classifies_new_fixed_and_persisted() {
         let formatter = JsonlFormatter::new();
 
-        // Create a very long snippet
-        let long_snippet = "a".repeat(10000);
-        let violations = vec![create_test_violation(
-            "test-rule",
-            "src/test.rs",
-            "src",
-            1,
-            1,
-            &long_snippet,
-            "long snippet",
-        )];
+        let baseline = AggregationResult {
+            statuses: vec![create_test_status(
+                "no-unwrap",
+                "src",
+                2,
+                5,
+                vec![
+                    create_test_violation("no-unwrap", "src/a.rs", "src", 1, 1, "kept", "m"),
+                    create_test_violation("no-unwrap", "src/b.rs", "src", 2, 1, "gone", "m"),
+                ],
+            )],
+            passed: true,
+            total_violations: 2,
+            violations_over_budget: 0,
+        };
 
-        let status = create_test_status("test-rule", "src", 1, 5, violations);
-        let result = AggregationResult {
-            statuses: vec![status],
+        let current = AggregationResult {
+            statuses: vec![create_test_status(
+                "no-unwrap",
+                "src",
+                2,
+                5,
+                vec![
+                    // Same fingerprint as baseline's "kept", but shifted to a
+                    // different line — still "persisted", not "new" + "fixed".
+                    create_test_violation("no-unwrap", "src/a.rs", "src", 9, 1, "kept", "m"),
+                    create_test_violation("no-unwrap", "src/c.rs", "src", 3, 1, "fresh", "m"),
+                ],
+            )],
             passed: true,
-            total_violations: 1,
+            total_violations: 2,
             violations_over_budget: 0,
         };
 
-        let output = formatter.format(&result, true);
-
-        // Verify the line is valid JSON
-        let lines: Vec<&str> = output.lines().collect();
-        let parsed: Result<serde_json::Value, _> = serde_json::from_str(lines[0]);
-        assert!(parsed.is_ok());
-
-        let violation: serde_json::Value = parsed.unwrap();
-        assert_eq!(violation["snippet"], long_snippet);
+        let output = formatter.format_delta(&baseline, &current, true);
+        let records: Vec<serde_json::Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        let find_change = |snippet: &str| {
+            records
+                .iter()
+                .filter(|v| v["type"] == "delta")
+                .find(|v| v["snippet"] == snippet)
+                .map(|v| v["change"].as_str().unwrap().to_string())
+                .unwrap()
+        };
+        assert_eq!(find_change("kept"), "persisted");
+        assert_eq!(find_change("gone"), "fixed");
+        assert_eq!(find_change("fresh"), "new");
     }
 
     #[test]
-    fn test_special_characters_in_messages() {
+    fn test_format_delta_reports_per_rule_added_removed_and_net() {
         let formatter = JsonlFormatter::new();
 
-        let violations = vec![
-            create_test_violation(
-                "test",
-                "src/test.rs",
+        let baseline = AggregationResult {
+            statuses: vec![create_test_status(
+                "no-unwrap",
                 "src",
                 1,
-                1,
-                "snippet",
-                "message with \"quotes\"",
-            ),
-            create_test_violation(
-                "test",
-                "src/test.rs",
+                5,
+                vec![create_test_violation(
+                    "no-unwrap",
+                    "src/a.rs",
+                    "src",
+                    1,
+                    1,
+                    "gone",
+                    "m",
+                )],
+            )],
+            passed: true,
+            total_violations: 1,
+            violations_over_budget: 0,
+        };
+
+        let current = AggregationResult {
+            statuses: vec![create_test_status(
+                "no-unwrap",
                 "src",
                 2,
-                1,
-                "snippet",
-                "message with 'apostrophe's",
-            ),
-            create_test_violation(
-                "test",
-                "src/test.rs",
-                "src",
-                3,
-                1,
-                "snippet",
-                "message\nwith\nnewlines",
-            ),
-        ];
-
-        let status = create_test_status("test", "src", 3, 10, violations);
-        let result = AggregationResult {
-            statuses: vec![status],
+                5,
+                vec![
+                    create_test_violation("no-unwrap", "src/b.rs", "src", 1, 1, "fresh1", "m"),
+                    create_test_violation("no-unwrap", "src/c.rs", "src", 2, 1, "fresh2", "m"),
+                ],
+            )],
             passed: true,
-            total_violations: 3,
+            total_violations: 2,
             violations_over_budget: 0,
         };
 
-        let output = formatter.format(&result, true);
-
-        // Verify all lines are valid JSON
-        for line in output.lines() {
-            let parsed: Result<serde_json::Value, _> = serde_json::from_str(line);
-            assert!(parsed.is_ok(), "Invalid JSON: {}", line);
-        }
-
-        // Verify messages are properly escaped
-        let lines: Vec<&str> = output.lines().collect();
-        let v1: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
-        let v2: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
-        let v3: serde_json::Value = serde_json::from_str(lines[2]).unwrap();
+        let output = formatter.format_delta(&baseline, &current, false);
+        let summary = output
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .find(|v| v["type"] == "delta_summary")
+            .unwrap();
 
-        assert_eq!(v1["message"], "message with \"quotes\"");
-        assert_eq!(v2["message"], "message with 'apostrophe's");
-        assert_eq!(v3["message"], "message\nwith\nnewlines");
+        assert_eq!(summary["rule"], "no-unwrap");
+        assert_eq!(summary["added"], 2);
+        assert_eq!(summary["removed"], 1);
+        assert_eq!(summary["net"], 1);
     }
 
     #[test]
-    fn test_region_status_with_no_violations() {
+    fn test_format_delta_status_fails_on_any_new_violation_regardless_of_budget() {
         let formatter = JsonlFormatter::new();
 
-        // Create a status with no violations but positive budget
-        let status = create_test_status("no-unwrap", "src", 0, 5, vec![]);
-        let result = AggregationResult {
-            statuses: vec![status],
+        let baseline = AggregationResult {
+            statuses: vec![],
             passed: true,
             total_violations: 0,
             violations_over_budget: 0,
         };
 
-        let output = formatter.format(&result, true);
-        let lines: Vec<&str> = output.lines().collect();
-
-        // Should have 2 lines: 1 summary + 1 status (no violation records)
-        assert_eq!(lines.len(), 2);
+        let current = AggregationResult {
+            statuses: vec![create_test_status(
+                "no-unwrap",
+                "src",
+                1,
+                // Well under budget, but a single new violation must still
+                // fail the delta status.
+                100,
+                vec![create_test_violation(
+                    "no-unwrap",
+                    "src/a.rs",
+                    "src",
+                    1,
+                    1,
+                    "fresh",
+                    "m",
+                )],
+            )],
+            passed: true,
+            total_violations: 1,
+            violations_over_budget: 0,
+        };
 
-        // Verify summary record
-        let summary: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
-        assert_eq!(summary["type"], "summary");
-        assert_eq!(summary["rule"], "no-unwrap");
-        assert_eq!(summary["violations"], 0);
-        assert_eq!(summary["budget"], 5);
-        assert_eq!(summary["status"], "pass");
+        let output = formatter.format_delta(&baseline, &current, false);
+        let status = output
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .find(|v| v["type"] == "delta_status")
+            .unwrap();
 
-        // Verify status record
-        let status: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
-        assert_eq!(status["type"], "status");
-        assert_eq!(status["passed"], true);
+        assert_eq!(status["passed"], false);
+        assert_eq!(status["new_violations"], 1);
     }
 
     #[test]
-    fn test_violation_sorting_by_file() {
+    fn test_format_delta_status_passes_when_only_persisted_violations_remain() {
         let formatter = JsonlFormatter::new();
 
-        // Create violations with same rule but different files
-        let violations = vec![
-            create_test_violation("rule-a", "src/z.rs", "src", 10, 5, "s1", "m1"),
-            create_test_violation("rule-a", "src/a.rs", "src", 10, 5, "s2", "m2"),
-            create_test_violation("rule-a", "src/m.rs", "src", 10, 5, "s3", "m3"),
-        ];
-
-        let status = create_test_status("rule-a", "src", 3, 5, violations);
-        let result = AggregationResult {
-            statuses: vec![status],
+        let baseline = AggregationResult {
+            statuses: vec![create_test_status(
+                "no-unwrap",
+                "src",
+                1,
+                5,
+                vec![create_test_violation(
+                    "no-unwrap",
+                    "src/a.rs",
+                    "src",
+                    1,
+                    1,
+                    "kept",
+                    "m",
+                )],
+            )],
             passed: true,
-            total_violations: 3,
+            total_violations: 1,
             violations_over_budget: 0,
         };
 
-        let output = formatter.format(&result, true);
-        let lines: Vec<&str> = output.lines().collect();
+        let current = AggregationResult {
+            statuses: vec![create_test_status(
+                "no-unwrap",
+                "src",
+                1,
+                5,
+                vec![create_test_violation(
+                    "no-unwrap",
+                    "src/a.rs",
+                    "src",
+                    1,
+                    1,
+                    "kept",
+                    "m",
+                )],
+            )],
+            passed: true,
+            total_violations: 1,
+            violations_over_budget: 0,
+        };
 
-        // Verify violations are sorted by file path
-        let v1: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
-        let v2: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
-        let v3: serde_json::Value = serde_json::from_str(lines[2]).unwrap();
+        let output = formatter.format_delta(&baseline, &current, false);
+        let status = output
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .find(|v| v["type"] == "delta_status")
+            .unwrap();
 
-        assert_eq!(v1["file"], "src/a.rs");
-        assert_eq!(v2["file"], "src/m.rs");
-        assert_eq!(v3["file"], "src/z.rs");
+        assert_eq!(status["passed"], true);
+        assert_eq!(status["new_violations"], 0);
+        assert_eq!(status["persisted_violations"], 1);
     }
 
     #[test]
-    fn test_format_non_verbose_hides_violation_records() {
-        // Test that when verbose=false, "type":"violation" records are not output
+    fn test_format_delta_without_verbose_omits_per_violation_records() {
         let formatter = JsonlFormatter::new();
-        let violations = vec![
-            create_test_violation(
-                "no-unwrap",
-                "src/main.rs",
-                "src",
-                10,
-                5,
-                ".unwrap()",
-                "Disallow .unwrap() calls",
-            ),
-            create_test_violation(
+
+        let baseline = AggregationResult {
+            statuses: vec![],
+            passed: true,
+            total_violations: 0,
+            violations_over_budget: 0,
+        };
+        let current = AggregationResult {
+            statuses: vec![create_test_status(
                 "no-unwrap",
-                "src/lib.rs",
                 "src",
-                20,
+                1,
                 5,
-                "result.unwrap()",
-                "Disallow .unwrap() calls",
-            ),
-        ];
-        let status = create_test_status("no-unwrap", "src", 2, 5, violations);
-        let result = AggregationResult {
-            statuses: vec![status],
+                vec![create_test_violation(
+                    "no-unwrap",
+                    "src/a.rs",
+                    "src",
+                    1,
+                    1,
+                    "fresh",
+                    "m",
+                )],
+            )],
             passed: true,
-            total_violations: 2,
+            total_violations: 1,
             violations_over_budget: 0,
         };
 
-        let output = formatter.format(&result, false);
-
-        // Parse each line as JSON
-        let lines: Vec<&str> = output.lines().collect();
-
-        // Assert no lines have "type":"violation"
-        for line in &lines {
-            let parsed: serde_json::Value = serde_json::from_str(line).unwrap();
-            assert_ne!(parsed["type"], "violation");
-        }
+        let output = formatter.format_delta(&baseline, &current, false);
 
-        // Assert there ARE lines with "type":"summary"
-        let has_summary = lines.iter().any(|line| {
-            let parsed: serde_json::Value = serde_json::from_str(line).unwrap();
-            parsed["type"] == "summary"
-        });
-        assert!(has_summary);
+        assert!(!output
+            .lines()
+            .any(|line| line.contains("\"type\":\"delta\"")));
+    }
 
-        // Assert there IS a line with "type":"status"
-        let has_status = lines.iter().any(|line| {
-            let parsed: serde_json::Value = serde_json::from_str(line).unwrap();
-            parsed["type"] == "status"
-        });
-        assert!(has_status);
+    #[test]
+    fn test_schema_describes_all_three_record_types() {
+        let schema = JsonlFormatter::schema();
+        let variants = schema["oneOf"].as_array().unwrap();
+
+        assert_eq!(variants.len(), 3);
+        let discriminators: Vec<&str> = variants
+            .iter()
+            .map(|v| v["properties"]["type"]["const"].as_str().unwrap())
+            .collect();
+        assert_eq!(discriminators, vec!["violation", "summary", "status"]);
     }
 
     #[test]
-    fn test_format_non_verbose_preserves_summary_records() {
-        // Test that summary and status records are still output when verbose=false
+    fn test_validate_output_passes_for_formatter_output() {
         let formatter = JsonlFormatter::new();
-
-        // Create multiple rules with violations
-        let violations1 = vec![create_test_violation(
+        let violations = vec![create_test_violation(
             "no-unwrap",
             "src/main.rs",
             "src",
             10,
             5,
             ".unwrap()",
-            "message",
+            "Disallow .unwrap() calls",
         )];
-        let violations2 = vec![
-            create_test_violation("no-todo", "src/lib.rs", "src", 20, 5, "// TODO", "message"),
-            create_test_violation("no-todo", "src/util.rs", "src", 30, 5, "// TODO", "message"),
-        ];
+        let status = create_test_status("no-unwrap", "src", 1, 5, violations);
+        let result = AggregationResult {
+            statuses: vec![status],
+            passed: true,
+            total_violations: 1,
+            violations_over_budget: 0,
+        };
 
-        let status1 = create_test_status("no-unwrap", "src", 1, 5, violations1);
-        let status2 = create_test_status("no-todo", "src", 2, 1, violations2);
+        let output = formatter.format(&result, true);
+
+        assert!(validate_output(&output).is_ok());
+    }
 
+    #[test]
+    fn test_validate_output_passes_for_empty_result() {
+        let formatter = JsonlFormatter::new();
         let result = AggregationResult {
-            statuses: vec![status1, status2],
-            passed: false,
-            total_violations: 3,
-            violations_over_budget: 1,
+            statuses: vec![],
+            passed: true,
+            total_violations: 0,
+            violations_over_budget: 0,
         };
 
-        let output = formatter.format(&result, false);
-        let lines: Vec<&str> = output.lines().collect();
+        let output = formatter.format(&result, true);
 
-        // Should have 3 lines: 2 summaries + 1 status (no violation records)
-        assert_eq!(lines.len(), 3);
+        assert!(validate_output(&output).is_ok());
+    }
 
-        // Verify first two lines are summaries
-        let summary1: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
-        let summary2: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
-        assert_eq!(summary1["type"], "summary");
-        assert_eq!(summary2["type"], "summary");
+    #[test]
+    fn test_validate_output_rejects_invalid_json() {
+        let error = validate_output("not json at all").unwrap_err();
+        assert!(matches!(error, SchemaError::InvalidJson { line: 1, .. }));
+    }
 
-        // Verify last line is status
-        let status: serde_json::Value = serde_json::from_str(lines[2]).unwrap();
-        assert_eq!(status["type"], "status");
-        assert_eq!(status["passed"], false);
-        assert_eq!(status["rules_checked"], 2);
-        assert_eq!(status["rules_exceeded"], 1);
-        assert_eq!(status["total_violations"], 3);
+    #[test]
+    fn test_validate_output_rejects_missing_required_field() {
+        let error = validate_output("{\"type\": \"status\", \"passed\": true}").unwrap_err();
+        match error {
+            SchemaError::SchemaMismatch { line, reason } => {
+                assert_eq!(line, 1);
+                assert!(reason.contains("rules_checked"));
+            }
+            other => panic!("expected SchemaMismatch, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_validate_output_rejects_wrong_field_type() {
+        let error = validate_output(
+            "{\"type\": \"status\", \"passed\": \"yes\", \"rules_checked\": 0, \"rules_exceeded\": 0, \"errors_exceeded\": 0, \"warnings_exceeded\": 0, \"total_violations\": 0, \"exit_code\": 0, \"source\": \"\"}",
+        )
+        .unwrap_err();
+        match error {
+            SchemaError::SchemaMismatch { reason, .. } => assert!(reason.contains("passed")),
+            other => panic!("expected SchemaMismatch, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_validate_output_rejects_unknown_record_type() {
+        let error = validate_output("{\"type\": \"mystery\"}").unwrap_err();
+        match error {
+            SchemaError::SchemaMismatch { reason, .. } => assert!(reason.contains("mystery")),
+            other => panic!("expected SchemaMismatch, got {other:?}"),
+        }
     }
 }
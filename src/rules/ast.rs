@@ -1,9 +1,18 @@
 #![forbid(unsafe_code)]
 
 //! AST-based rule execution using tree-sitter
+//!
+//! [`AstRule`] matches a rule's `[match]` configuration against a parsed
+//! tree, either via a compiled tree-sitter query or a `$name`-templated code
+//! pattern matched by structural unification; [`ParserCache`] owns the
+//! parsers and compiled queries that back it.
 
 mod ast_rule;
+mod cache;
+mod language_registry;
 mod parser_cache;
 
-pub use ast_rule::AstRule;
+pub use ast_rule::{AstRule, AstRuleError};
+pub use cache::{Cache, Cached};
+pub use language_registry::{LanguageConfig, LanguageRegistry};
 pub use parser_cache::ParserCache;
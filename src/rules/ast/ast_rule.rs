@@ -0,0 +1,905 @@
+#![forbid(unsafe_code)]
+
+//! Structural rules matched against a parsed tree-sitter tree
+//!
+//! An [`AstRule`] matches a parsed tree one of two ways, set in
+//! [`AstRule::from_toml`] by whether the TOML body has a `query` or a
+//! `pattern` key:
+//!
+//! - `query` compiles a raw tree-sitter S-expression once; every match of
+//!   its `@violation` capture (`@violation` unless `capture` overrides it)
+//!   produces one [`Violation`].
+//! - `pattern` instead takes a small code snippet with `$name` placeholders
+//!   (e.g. `"$expr.unwrap()"`), parsed with the same grammar as the target
+//!   language. Matching walks the target tree node by node and attempts
+//!   structural unification against the pattern's own parse tree: node
+//!   kinds must agree, named children must unify pairwise in order
+//!   (comment nodes aside), a placeholder leaf matches any candidate
+//!   subtree and binds it under its name, and a placeholder repeated later
+//!   in the pattern must bind to byte-identical source text. This is the
+//!   friendlier authoring surface for users who'd rather write `$expr.unwrap()`
+//!   than the equivalent tree-sitter query.
+//!
+//! Either way, [`AstRule::execute_with_tree`] reports one [`Violation`] per
+//! surviving match, anchored at the match's root node.
+
+use super::parser_cache::{ParserCache, ParserError};
+use crate::config::autofix::Edit;
+use crate::rules::Violation;
+use crate::types::{Language, RegionPath, RuleId};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use thiserror::Error;
+use tree_sitter::{Node, Parser, Query, QueryCursor, Tree};
+
+/// Errors building or running an [`AstRule`]
+#[derive(Debug, Error)]
+pub enum AstRuleError {
+    /// The rule's TOML body didn't parse, or was missing a required field
+    #[error("invalid AST rule TOML: {0}")]
+    InvalidToml(String),
+
+    /// `rule_id` failed [`RuleId`]'s own validation
+    #[error("rule '{0}' has an invalid rule_id")]
+    InvalidRuleId(String),
+
+    /// The rule's `query` failed to compile against the target grammar
+    #[error("rule '{0}' query failed to compile: {1}")]
+    QueryCompilationFailed(String, String),
+
+    /// `query` never binds the capture named by `capture` (`@violation` by default)
+    #[error("rule '{0}' query has no capture named '@{1}'")]
+    MissingViolationCapture(String, String),
+
+    /// The rule's TOML body set neither `query` nor `pattern`
+    #[error("rule '{0}' must set exactly one of `query` or `pattern`")]
+    MissingMatcher(String),
+
+    /// The rule's TOML body set both `query` and `pattern`
+    #[error("rule '{0}' set both `query` and `pattern`; only one is allowed")]
+    AmbiguousMatcher(String),
+
+    /// The rule's `pattern` failed to parse against the target grammar, or
+    /// didn't resolve to a single meaningful node
+    #[error("rule '{0}' pattern failed to parse: {1}")]
+    PatternCompilationFailed(String, String),
+
+    /// The rule's language has no compiled-in grammar available
+    #[error(transparent)]
+    Parser(#[from] ParserError),
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct RawAstRule {
+    rule_id: String,
+    #[serde(default)]
+    query: Option<String>,
+    #[serde(default)]
+    pattern: Option<String>,
+    #[serde(default = "default_violation_capture")]
+    capture: String,
+    message: String,
+    #[serde(default)]
+    fix: Option<RawFix>,
+    #[serde(default, rename = "match")]
+    match_options: RawMatchOptions,
+}
+
+fn default_violation_capture() -> String {
+    "violation".to_string()
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct RawFix {
+    replacement: String,
+}
+
+#[derive(Debug, Default, serde::Deserialize)]
+struct RawMatchOptions {
+    #[serde(default)]
+    dedup_nested: bool,
+    #[serde(default)]
+    allow_in: Vec<String>,
+    #[serde(default)]
+    deny_in: Vec<String>,
+}
+
+/// One `@capture`/`$placeholder` binding from a match, carried alongside the
+/// violation node so [`AstRule::fix_for`] can substitute it into a `[fix]` template
+type Capture<'tree> = (String, Node<'tree>);
+
+/// Either way an [`AstRule`] matches a parsed tree; see the module doc comment
+enum Matcher {
+    /// A compiled tree-sitter query; every match binding `violation_capture_index` is a hit
+    Query {
+        query: Query,
+        violation_capture_index: u32,
+    },
+    /// A `$name`-templated code pattern, matched by structural unification
+    Pattern(PatternMatcher),
+}
+
+impl Matcher {
+    fn matches_in<'tree>(
+        &self,
+        tree: &'tree Tree,
+        source: &str,
+    ) -> Vec<(Node<'tree>, Vec<Capture<'tree>>)> {
+        match self {
+            Matcher::Query {
+                query,
+                violation_capture_index,
+            } => {
+                let mut query_cursor = QueryCursor::new();
+                let mut matches = Vec::new();
+
+                for query_match in query_cursor.matches(query, tree.root_node(), source.as_bytes())
+                {
+                    let mut violation_node = None;
+                    let mut captures = Vec::with_capacity(query_match.captures.len());
+                    for capture in query_match.captures {
+                        let name = query.capture_names()[capture.index as usize].to_string();
+                        if capture.index == *violation_capture_index {
+                            violation_node = Some(capture.node);
+                        }
+                        captures.push((name, capture.node));
+                    }
+
+                    if let Some(node) = violation_node {
+                        matches.push((node, captures));
+                    }
+                }
+
+                matches
+            }
+            Matcher::Pattern(matcher) => matcher.matches_in(tree, source),
+        }
+    }
+}
+
+/// A compiled `pattern = "..."` template, matched by structural unification
+/// against a target tree rather than a tree-sitter query
+///
+/// Built by [`PatternMatcher::compile`]: `$name` placeholders are stripped
+/// down to bare identifiers (`name`) so the pattern parses as ordinary code
+/// against `language`'s own grammar, producing a small template tree. The
+/// stripped placeholder names are remembered so matching can recognize the
+/// corresponding identifier leaves again and treat them as wildcards.
+struct PatternMatcher {
+    /// The parsed (placeholder-stripped) pattern, wrapped in whatever
+    /// surrounding syntax the grammar needs to parse it as a real node
+    template_tree: Tree,
+    /// `template_tree`'s source text, i.e. the wrapped, stripped pattern
+    template_source: String,
+    /// The byte range of the pattern itself (excluding the wrapper) within `template_source`
+    template_range: (usize, usize),
+    /// The `$name`s the pattern declared, by their stripped (sigil-less) spelling
+    placeholders: HashSet<String>,
+}
+
+impl PatternMatcher {
+    /// Compiles `pattern_src` (e.g. `"$expr.unwrap()"`) against `language`'s grammar
+    fn compile(language: Language, rule_id: &str, pattern_src: &str) -> Result<Self, AstRuleError> {
+        let (stripped, placeholders) = strip_placeholder_sigils(pattern_src);
+        let (wrapped, offset) = wrap_pattern_for_parsing(language, &stripped);
+
+        let ts_language = ParserCache::ts_language(language)?;
+        let mut parser = Parser::new();
+        parser.set_language(&ts_language).map_err(|e| {
+            AstRuleError::PatternCompilationFailed(rule_id.to_string(), e.to_string())
+        })?;
+        let template_tree = parser.parse(&wrapped, None).ok_or_else(|| {
+            AstRuleError::PatternCompilationFailed(
+                rule_id.to_string(),
+                "parser produced no tree".to_string(),
+            )
+        })?;
+
+        let template_range = (offset, offset + stripped.len());
+        let root_kind = template_tree
+            .root_node()
+            .descendant_for_byte_range(template_range.0, template_range.1)
+            .ok_or_else(|| {
+                AstRuleError::PatternCompilationFailed(
+                    rule_id.to_string(),
+                    "pattern did not resolve to a single node".to_string(),
+                )
+            })?
+            .kind();
+        if root_kind == "ERROR" {
+            return Err(AstRuleError::PatternCompilationFailed(
+                rule_id.to_string(),
+                "pattern did not parse cleanly against the target grammar".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            template_tree,
+            template_source: wrapped,
+            template_range,
+            placeholders,
+        })
+    }
+
+    /// The pattern's own root node, re-derived from `template_tree` each call
+    fn root(&self) -> Node<'_> {
+        self.template_tree
+            .root_node()
+            .descendant_for_byte_range(self.template_range.0, self.template_range.1)
+            .expect("validated in PatternMatcher::compile")
+    }
+
+    /// Finds every node in `tree` whose subtree structurally unifies with this pattern
+    fn matches_in<'tree>(
+        &self,
+        tree: &'tree Tree,
+        source: &str,
+    ) -> Vec<(Node<'tree>, Vec<Capture<'tree>>)> {
+        let root_kind = self.root().kind();
+        let mut matches = Vec::new();
+        visit_all(tree.root_node(), &mut |candidate| {
+            if candidate.kind() != root_kind {
+                return;
+            }
+            let mut bindings: HashMap<String, Node<'tree>> = HashMap::new();
+            if unify(
+                self.root(),
+                candidate,
+                &self.template_source,
+                source,
+                &self.placeholders,
+                &mut bindings,
+            ) {
+                let captures = bindings.into_iter().collect();
+                matches.push((candidate, captures));
+            }
+        });
+        matches
+    }
+}
+
+/// A structural rule matched against a parsed syntax tree, either via a
+/// tree-sitter query or a `$name`-templated code pattern; see the module doc comment
+///
+/// Every surviving match produces one [`Violation`], anchored at the
+/// match's root node (the `@violation` capture for a query rule, or the
+/// node the whole pattern unified against for a pattern rule).
+///
+/// An optional `[fix]` section's `replacement` template (e.g. `"@recv?"` to
+/// rewrite `x.unwrap()` to `x?`) is rendered per match by
+/// [`AstRule::fix_for`], substituting each `@name` token with the source text
+/// of that match's same-named capture — for a pattern rule, captures are its
+/// `$name` placeholder bindings, so `"@expr?"` works against `pattern =
+/// "$expr.unwrap()"` the same way `"@recv?"` works against the equivalent query.
+///
+/// A `[match]` table's `allow_in`/`deny_in` lists of ancestor node kinds
+/// narrow which matches apply: `allow_in` keeps a candidate only if some
+/// ancestor's kind is in the list (a whitelist of contexts the rule applies
+/// in); `deny_in` drops a candidate if some ancestor's kind is in the list (a
+/// blacklist), e.g. excluding matches inside test modules without a separate
+/// path glob. Once both have run, `dedup_nested = true` discards a candidate
+/// whose byte span is fully contained within another surviving candidate's
+/// span, keeping only the outermost (e.g. `x.unwrap().unwrap()` reports once
+/// instead of twice); absent the flag, every match is reported.
+pub struct AstRule {
+    /// The rule's identifier, as used in budgets and violation reports
+    pub rule_id: RuleId,
+    /// The language the rule is matched against
+    pub language: Language,
+    matcher: Matcher,
+    message: String,
+    fix_replacement: Option<String>,
+    dedup_nested: bool,
+    allow_in: Vec<String>,
+    deny_in: Vec<String>,
+}
+
+impl AstRule {
+    /// Compiles an AST rule from its TOML body, against `language`'s grammar
+    ///
+    /// The TOML body must set exactly one of `query` (a raw tree-sitter
+    /// S-expression) or `pattern` (a `$name`-templated code snippet); see
+    /// the module doc comment for how each is matched.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the TOML doesn't parse, `rule_id` is invalid,
+    /// neither or both of `query`/`pattern` are set, `language` has no
+    /// compiled-in grammar, `query` fails to compile against it, `query`
+    /// never binds the `capture` name (`@violation` by default), or
+    /// `pattern` doesn't parse cleanly against the grammar.
+    pub fn from_toml(language: Language, toml_str: &str) -> Result<Self, AstRuleError> {
+        let raw: RawAstRule =
+            toml::from_str(toml_str).map_err(|e| AstRuleError::InvalidToml(e.to_string()))?;
+
+        let rule_id = RuleId::new(&raw.rule_id)
+            .map_err(|_| AstRuleError::InvalidRuleId(raw.rule_id.clone()))?;
+
+        let matcher = match (&raw.query, &raw.pattern) {
+            (Some(_), Some(_)) => return Err(AstRuleError::AmbiguousMatcher(raw.rule_id)),
+            (None, None) => return Err(AstRuleError::MissingMatcher(raw.rule_id)),
+            (Some(query_src), None) => {
+                let ts_language = ParserCache::ts_language(language)?;
+                let query = Query::new(&ts_language, query_src).map_err(|e| {
+                    AstRuleError::QueryCompilationFailed(raw.rule_id.clone(), e.to_string())
+                })?;
+                let violation_capture_index =
+                    query.capture_index_for_name(&raw.capture).ok_or_else(|| {
+                        AstRuleError::MissingViolationCapture(
+                            raw.rule_id.clone(),
+                            raw.capture.clone(),
+                        )
+                    })?;
+                Matcher::Query {
+                    query,
+                    violation_capture_index,
+                }
+            }
+            (None, Some(pattern_src)) => Matcher::Pattern(PatternMatcher::compile(
+                language,
+                &raw.rule_id,
+                pattern_src,
+            )?),
+        };
+
+        Ok(Self {
+            rule_id,
+            language,
+            matcher,
+            message: raw.message,
+            fix_replacement: raw.fix.map(|fix| fix.replacement),
+            dedup_nested: raw.match_options.dedup_nested,
+            allow_in: raw.match_options.allow_in,
+            deny_in: raw.match_options.deny_in,
+        })
+    }
+
+    /// Runs this rule's query or pattern against an already-parsed `tree`, returning one violation per match
+    pub fn execute_with_tree(&self, tree: &Tree, source: &str, path: &Path) -> Vec<Violation> {
+        self.matches_in(tree, source)
+            .into_iter()
+            .map(|(node, _captures)| self.violation_for(node, source, path))
+            .collect()
+    }
+
+    /// Like [`AstRule::execute_with_tree`], additionally rendering each match's `[fix]` edit, if any
+    pub fn execute_with_fixes(
+        &self,
+        tree: &Tree,
+        source: &str,
+        path: &Path,
+    ) -> Vec<(Violation, Option<Edit>)> {
+        self.matches_in(tree, source)
+            .into_iter()
+            .map(|(node, captures)| {
+                let violation = self.violation_for(node, source, path);
+                let fix = self.fix_for(node, &captures, source);
+                (violation, fix)
+            })
+            .collect()
+    }
+
+    /// Builds this rule's `[fix]` edit for one matched `@violation` node, if configured
+    ///
+    /// Returns `None` if the rule has no `[fix]` section, or if the template
+    /// references a capture name this particular match didn't bind.
+    fn fix_for(&self, node: Node, captures: &[Capture], source: &str) -> Option<Edit> {
+        let template = self.fix_replacement.as_ref()?;
+        let replacement = render_fix_template(template, captures, source)?;
+        Some(Edit {
+            span: node.start_byte()..node.end_byte(),
+            replacement,
+        })
+    }
+
+    /// Runs this rule's matcher, collecting every surviving match's root node and its captures
+    fn matches_in<'tree>(
+        &self,
+        tree: &'tree Tree,
+        source: &str,
+    ) -> Vec<(Node<'tree>, Vec<Capture<'tree>>)> {
+        let mut matches = self.matcher.matches_in(tree, source);
+        matches.retain(|(node, _)| self.passes_context_constraints(*node));
+
+        if self.dedup_nested {
+            dedup_nested_matches(&mut matches);
+        }
+
+        matches
+    }
+
+    fn passes_context_constraints(&self, node: Node) -> bool {
+        if !self.allow_in.is_empty() && !has_ancestor_kind(node, &self.allow_in) {
+            return false;
+        }
+        if !self.deny_in.is_empty() && has_ancestor_kind(node, &self.deny_in) {
+            return false;
+        }
+        true
+    }
+
+    fn violation_for(&self, node: Node, source: &str, path: &Path) -> Violation {
+        let start = node.start_position();
+        let end = node.end_position();
+        let region = path
+            .parent()
+            .map(|parent| parent.to_string_lossy().into_owned())
+            .filter(|parent| !parent.is_empty())
+            .unwrap_or_else(|| ".".to_string());
+
+        Violation {
+            rule_id: self.rule_id.clone(),
+            file: path.to_path_buf(),
+            line: start.row as u32 + 1,
+            column: start.column as u32 + 1,
+            end_line: end.row as u32 + 1,
+            end_column: end.column as u32 + 1,
+            snippet: node.utf8_text(source.as_bytes()).unwrap_or("").to_string(),
+            message: self.message.clone(),
+            region: RegionPath::new(region),
+            weight: 1,
+        }
+    }
+}
+
+/// Walks `node`'s ancestor chain, true if any ancestor's kind is in `kinds`
+fn has_ancestor_kind(node: Node, kinds: &[String]) -> bool {
+    let mut current = node.parent();
+    while let Some(ancestor) = current {
+        if kinds.iter().any(|kind| kind == ancestor.kind()) {
+            return true;
+        }
+        current = ancestor.parent();
+    }
+    false
+}
+
+/// Discards a candidate whose span is fully contained within another's, keeping the outermost
+///
+/// Sorts by start ascending, length descending, then keeps a candidate only
+/// if it isn't contained in a previously kept one.
+fn dedup_nested_matches(matches: &mut Vec<(Node, Vec<Capture>)>) {
+    matches.sort_by_key(|(node, _)| (node.start_byte(), std::cmp::Reverse(node.end_byte())));
+
+    let mut kept_spans: Vec<(usize, usize)> = Vec::new();
+    matches.retain(|(node, _)| {
+        let span = (node.start_byte(), node.end_byte());
+        let contained = kept_spans
+            .iter()
+            .any(|&(start, end)| start <= span.0 && span.1 <= end);
+        if contained {
+            false
+        } else {
+            kept_spans.push(span);
+            true
+        }
+    });
+}
+
+/// Substitutes `@name` tokens in `template` with the source text of the same-named capture
+///
+/// Everything outside a `@name` token (including, e.g., the trailing `?` in
+/// the common `.unwrap()` -> `?`-operator rewrite `"@recv?"`) passes through
+/// unchanged. Returns `None` if `template` references a capture name that
+/// wasn't bound by this particular match.
+fn render_fix_template(template: &str, captures: &[Capture], source: &str) -> Option<String> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(at) = rest.find('@') {
+        rendered.push_str(&rest[..at]);
+        rest = &rest[at + 1..];
+
+        let name_len = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+        let (name, remainder) = rest.split_at(name_len);
+
+        let node = captures
+            .iter()
+            .find(|(capture_name, _)| capture_name == name)
+            .map(|(_, node)| *node)?;
+        rendered.push_str(node.utf8_text(source.as_bytes()).ok()?);
+        rest = remainder;
+    }
+    rendered.push_str(rest);
+    Some(rendered)
+}
+
+/// Strips the `$` sigil from every `$name` placeholder in `pattern`, returning
+/// the now-ordinary-looking source text alongside the set of names it found
+///
+/// `$expr.unwrap()` becomes `expr.unwrap()` plus `{"expr"}`: stripping the
+/// sigil lets the result parse as ordinary code against the target grammar,
+/// while the returned names let matching recognize those same identifier
+/// leaves again and treat them as wildcards instead of literal text.
+fn strip_placeholder_sigils(pattern: &str) -> (String, HashSet<String>) {
+    let mut stripped = String::with_capacity(pattern.len());
+    let mut placeholders = HashSet::new();
+    let mut rest = pattern;
+
+    while let Some(at) = rest.find('$') {
+        stripped.push_str(&rest[..at]);
+        rest = &rest[at + 1..];
+
+        let name_len = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+        let (name, remainder) = rest.split_at(name_len);
+
+        placeholders.insert(name.to_string());
+        stripped.push_str(name);
+        rest = remainder;
+    }
+    stripped.push_str(rest);
+    (stripped, placeholders)
+}
+
+/// Wraps a stripped pattern in whatever surrounding syntax `language`'s
+/// grammar needs to parse it as a real expression/statement node, returning
+/// the wrapped source alongside the byte offset the pattern starts at
+///
+/// A bare `expr.unwrap()` isn't a complete Rust (or Go, or JS/TS) source
+/// file on its own, so each language gets just enough of a shell — a
+/// function body is plenty — for its grammar to parse the pattern as an
+/// ordinary node rather than falling into error recovery.
+fn wrap_pattern_for_parsing(language: Language, stripped: &str) -> (String, usize) {
+    match language {
+        Language::Rust => {
+            let prefix = "fn __ratchet_pattern__() {\n    ";
+            (format!("{prefix}{stripped}\n}}\n"), prefix.len())
+        }
+        Language::JavaScript | Language::TypeScript => {
+            let prefix = "function __ratchet_pattern__() {\n    ";
+            (format!("{prefix}{stripped}\n}}\n"), prefix.len())
+        }
+        Language::Go => {
+            let prefix = "func __ratchet_pattern__() {\n\t";
+            (format!("{prefix}{stripped}\n}}\n"), prefix.len())
+        }
+        Language::Python => {
+            let prefix = "def __ratchet_pattern__():\n    ";
+            (format!("{prefix}{stripped}\n"), prefix.len())
+        }
+        _ => (stripped.to_string(), 0),
+    }
+}
+
+/// Calls `visit` on `node` and then, recursively, every descendant (named or not)
+fn visit_all<'tree>(node: Node<'tree>, visit: &mut impl FnMut(Node<'tree>)) {
+    visit(node);
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        visit_all(child, visit);
+    }
+}
+
+/// `template`'s named children, skipping comment nodes, which shouldn't affect alignment
+fn named_children_skipping_trivia(node: Node) -> Vec<Node> {
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor)
+        .filter(|child| !child.kind().contains("comment"))
+        .collect()
+}
+
+/// True if `template` is a placeholder leaf: a childless node whose text is one of `placeholders`
+fn is_placeholder(template: Node, template_source: &str, placeholders: &HashSet<String>) -> bool {
+    if template.named_child_count() != 0 {
+        return false;
+    }
+    template
+        .utf8_text(template_source.as_bytes())
+        .is_ok_and(|text| placeholders.contains(text))
+}
+
+/// Attempts to structurally unify `template` against `candidate`, recording placeholder bindings
+///
+/// A placeholder leaf matches any candidate subtree and binds it under its
+/// name; a placeholder bound more than once must bind to byte-identical
+/// source text each time. Otherwise, the two nodes must share a kind, and
+/// (skipping comment children) either both be leaves with identical text or
+/// have the same number of named children, each unifying pairwise in order.
+fn unify<'tree>(
+    template: Node,
+    candidate: Node<'tree>,
+    template_source: &str,
+    source: &str,
+    placeholders: &HashSet<String>,
+    bindings: &mut HashMap<String, Node<'tree>>,
+) -> bool {
+    if is_placeholder(template, template_source, placeholders) {
+        let name = template.utf8_text(template_source.as_bytes()).unwrap_or("");
+        let candidate_text = candidate.utf8_text(source.as_bytes()).unwrap_or("");
+        return match bindings.get(name) {
+            Some(bound) => bound.utf8_text(source.as_bytes()).unwrap_or("") == candidate_text,
+            None => {
+                bindings.insert(name.to_string(), candidate);
+                true
+            }
+        };
+    }
+
+    if template.kind() != candidate.kind() {
+        return false;
+    }
+
+    let template_children = named_children_skipping_trivia(template);
+    let candidate_children = named_children_skipping_trivia(candidate);
+
+    if template_children.is_empty() && candidate_children.is_empty() {
+        let template_text = template.utf8_text(template_source.as_bytes()).unwrap_or("");
+        let candidate_text = candidate.utf8_text(source.as_bytes()).unwrap_or("");
+        return template_text == candidate_text;
+    }
+
+    template_children.len() == candidate_children.len()
+        && template_children
+            .into_iter()
+            .zip(candidate_children)
+            .all(|(t, c)| unify(t, c, template_source, source, placeholders, bindings))
+}
+
+#[cfg(test)]
+#[cfg(feature = "lang-rust")]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> Tree {
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&tree_sitter_rust::language()).unwrap();
+        parser.parse(source, None).unwrap()
+    }
+
+    fn unwrap_rule(toml_str: &str) -> AstRule {
+        AstRule::from_toml(Language::Rust, toml_str).unwrap()
+    }
+
+    const UNWRAP_RULE: &str = r#"
+rule_id = "no-unwrap"
+query = "(call_expression function: (field_expression field: (field_identifier) @method) @violation (#eq? @method \"unwrap\"))"
+message = "avoid .unwrap()"
+"#;
+
+    #[test]
+    fn test_from_toml_rejects_missing_violation_capture() {
+        let result = AstRule::from_toml(
+            Language::Rust,
+            r#"
+rule_id = "no-unwrap"
+query = "(call_expression) @call"
+message = "avoid .unwrap()"
+"#,
+        );
+        assert!(matches!(
+            result,
+            Err(AstRuleError::MissingViolationCapture(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_from_toml_rejects_invalid_query() {
+        let result = AstRule::from_toml(
+            Language::Rust,
+            r#"
+rule_id = "no-unwrap"
+query = "(not a valid query"
+message = "avoid .unwrap()"
+"#,
+        );
+        assert!(matches!(
+            result,
+            Err(AstRuleError::QueryCompilationFailed(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_execute_with_tree_reports_one_violation_per_match() {
+        let rule = unwrap_rule(UNWRAP_RULE);
+        let source = "fn f() { a.unwrap(); b.unwrap(); }";
+        let tree = parse(source);
+
+        let violations = rule.execute_with_tree(&tree, source, Path::new("src/lib.rs"));
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].rule_id, rule.rule_id);
+        assert_eq!(violations[0].message, "avoid .unwrap()");
+        assert_eq!(violations[0].snippet, "a.unwrap()");
+    }
+
+    #[test]
+    fn test_fix_for_renders_template_from_captures() {
+        let toml_str = r#"
+rule_id = "no-unwrap"
+query = "(call_expression function: (field_expression value: (_) @recv field: (field_identifier) @method) @violation (#eq? @method \"unwrap\"))"
+message = "avoid .unwrap()"
+
+[fix]
+replacement = "@recv?"
+"#;
+        let rule = unwrap_rule(toml_str);
+        let source = "fn f() { a.unwrap(); }";
+        let tree = parse(source);
+
+        let results = rule.execute_with_fixes(&tree, source, Path::new("src/lib.rs"));
+        assert_eq!(results.len(), 1);
+        let (_, fix) = &results[0];
+        let edit = fix.as_ref().expect("rule has a [fix] section");
+        assert_eq!(edit.replacement, "a?");
+        assert_eq!(&source[edit.span.clone()], "a.unwrap()");
+    }
+
+    #[test]
+    fn test_fix_for_is_none_without_fix_section() {
+        let rule = unwrap_rule(UNWRAP_RULE);
+        let source = "fn f() { a.unwrap(); }";
+        let tree = parse(source);
+
+        let results = rule.execute_with_fixes(&tree, source, Path::new("src/lib.rs"));
+        assert!(results[0].1.is_none());
+    }
+
+    #[test]
+    fn test_dedup_nested_keeps_only_outermost_match() {
+        let mut rule = unwrap_rule(UNWRAP_RULE);
+        rule.dedup_nested = true;
+        let source = "fn f() { a.unwrap().unwrap(); }";
+        let tree = parse(source);
+
+        let violations = rule.execute_with_tree(&tree, source, Path::new("src/lib.rs"));
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].snippet, "a.unwrap().unwrap()");
+    }
+
+    #[test]
+    fn test_without_dedup_nested_reports_every_match() {
+        let rule = unwrap_rule(UNWRAP_RULE);
+        let source = "fn f() { a.unwrap().unwrap(); }";
+        let tree = parse(source);
+
+        let violations = rule.execute_with_tree(&tree, source, Path::new("src/lib.rs"));
+        assert_eq!(violations.len(), 2);
+    }
+
+    #[test]
+    fn test_deny_in_suppresses_matches_inside_denied_ancestor() {
+        let mut rule = unwrap_rule(UNWRAP_RULE);
+        rule.deny_in = vec!["function_item".to_string()];
+        let source = "fn f() { a.unwrap(); }";
+        let tree = parse(source);
+
+        assert!(
+            rule.execute_with_tree(&tree, source, Path::new("src/lib.rs"))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_allow_in_keeps_only_matches_inside_allowed_ancestor() {
+        let mut rule = unwrap_rule(UNWRAP_RULE);
+        rule.allow_in = vec!["closure_expression".to_string()];
+        let source = "fn f() { a.unwrap(); let g = || b.unwrap(); }";
+        let tree = parse(source);
+
+        let violations = rule.execute_with_tree(&tree, source, Path::new("src/lib.rs"));
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].snippet, "b.unwrap()");
+    }
+
+    #[test]
+    fn test_from_toml_rejects_query_and_pattern_together() {
+        let result = AstRule::from_toml(
+            Language::Rust,
+            r#"
+rule_id = "no-unwrap"
+query = "(call_expression) @violation"
+pattern = "$expr.unwrap()"
+message = "avoid .unwrap()"
+"#,
+        );
+        assert!(matches!(result, Err(AstRuleError::AmbiguousMatcher(_))));
+    }
+
+    #[test]
+    fn test_from_toml_rejects_missing_query_and_pattern() {
+        let result = AstRule::from_toml(
+            Language::Rust,
+            r#"
+rule_id = "no-unwrap"
+message = "avoid .unwrap()"
+"#,
+        );
+        assert!(matches!(result, Err(AstRuleError::MissingMatcher(_))));
+    }
+
+    #[test]
+    fn test_from_toml_rejects_pattern_that_fails_to_parse() {
+        let result = AstRule::from_toml(
+            Language::Rust,
+            r#"
+rule_id = "no-unwrap"
+pattern = ")))"
+message = "avoid .unwrap()"
+"#,
+        );
+        assert!(matches!(
+            result,
+            Err(AstRuleError::PatternCompilationFailed(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_pattern_rule_matches_structurally_equivalent_code() {
+        let rule = unwrap_rule(
+            r#"
+rule_id = "no-unwrap"
+pattern = "$expr.unwrap()"
+message = "avoid .unwrap()"
+"#,
+        );
+        let source = "fn f() { a.unwrap(); b.method_call(); c.d.unwrap(); }";
+        let tree = parse(source);
+
+        let violations = rule.execute_with_tree(&tree, source, Path::new("src/lib.rs"));
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].snippet, "a.unwrap()");
+        assert_eq!(violations[0].message, "avoid .unwrap()");
+        assert_eq!(violations[1].snippet, "c.d.unwrap()");
+    }
+
+    #[test]
+    fn test_pattern_rule_requires_repeated_placeholder_to_bind_identical_text() {
+        let rule = unwrap_rule(
+            r#"
+rule_id = "self-compare"
+pattern = "$x == $x"
+message = "suspicious self-comparison"
+"#,
+        );
+        let source = "fn f() { let _ = a == a; let _ = a == b; }";
+        let tree = parse(source);
+
+        let violations = rule.execute_with_tree(&tree, source, Path::new("src/lib.rs"));
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].snippet, "a == a");
+    }
+
+    #[test]
+    fn test_pattern_rule_fix_for_renders_from_placeholder_capture() {
+        let rule = unwrap_rule(
+            r#"
+rule_id = "no-unwrap"
+pattern = "$expr.unwrap()"
+message = "avoid .unwrap()"
+
+[fix]
+replacement = "@expr?"
+"#,
+        );
+        let source = "fn f() { a.unwrap(); }";
+        let tree = parse(source);
+
+        let results = rule.execute_with_fixes(&tree, source, Path::new("src/lib.rs"));
+        assert_eq!(results.len(), 1);
+        let (_, fix) = &results[0];
+        let edit = fix.as_ref().expect("rule has a [fix] section");
+        assert_eq!(edit.replacement, "a?");
+        assert_eq!(&source[edit.span.clone()], "a.unwrap()");
+    }
+
+    #[test]
+    fn test_pattern_rule_respects_dedup_nested() {
+        let mut rule = unwrap_rule(
+            r#"
+rule_id = "no-unwrap"
+pattern = "$expr.unwrap()"
+message = "avoid .unwrap()"
+"#,
+        );
+        rule.dedup_nested = true;
+        let source = "fn f() { a.unwrap().unwrap(); }";
+        let tree = parse(source);
+
+        let violations = rule.execute_with_tree(&tree, source, Path::new("src/lib.rs"));
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].snippet, "a.unwrap().unwrap()");
+    }
+}
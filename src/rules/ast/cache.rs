@@ -0,0 +1,116 @@
+//! A small, reusable "build it once, keyed by something, cache it" subsystem
+//!
+//! Factors out the double-checked-locking pattern `ParserCache` already uses
+//! twice (once for parser pools, once for compiled queries) into a generic
+//! [`Cache`] over anything implementing [`Cached`], so a new lazily-built,
+//! shareable resource doesn't need to reimplement the locking by hand.
+
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::RwLock;
+
+/// Knows how to lazily build the value a [`Cache`] stores for a given key
+///
+/// `Key` isn't fixed to [`crate::types::Language`]: `ParserCache` alone needs
+/// two different key shapes (a bare language for parser pools, a
+/// `(language, query kind)` pair for compiled queries), so the cache is
+/// generic over both the key and the value it produces.
+pub trait Cached {
+    /// The key values are looked up and built by
+    type Key: Copy + Eq + Hash;
+    /// The value stored and handed back by the cache; cheaply cloneable
+    /// (typically an `Arc<_>`) since `get` returns an owned copy
+    type Output: Clone;
+    /// The error a failed build, or a poisoned internal lock, produces
+    type Error;
+
+    /// Builds the value for `key`
+    fn build(&self, key: Self::Key) -> Result<Self::Output, Self::Error>;
+
+    /// Produces the error to return if the cache's internal lock is poisoned
+    fn lock_poisoned() -> Self::Error;
+}
+
+/// A lazily-populated, thread-safe cache of `C::Output` keyed by `C::Key`
+///
+/// Uses double-checked locking: a cheap read-lock check first, and only on a
+/// miss does it take the write lock to build and insert the value.
+pub struct Cache<C: Cached> {
+    builder: C,
+    entries: RwLock<HashMap<C::Key, C::Output>>,
+}
+
+impl<C: Cached> Cache<C> {
+    /// Creates an empty cache backed by `builder`
+    pub fn new(builder: C) -> Self {
+        Self {
+            builder,
+            entries: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Gets the cached value for `key`, building and storing it on a miss
+    pub fn get(&self, key: C::Key) -> Result<C::Output, C::Error> {
+        {
+            let entries = self.entries.read().map_err(|_| C::lock_poisoned())?;
+            if let Some(value) = entries.get(&key) {
+                return Ok(value.clone());
+            }
+        }
+
+        let mut entries = self.entries.write().map_err(|_| C::lock_poisoned())?;
+        if let Entry::Vacant(e) = entries.entry(key) {
+            let value = self.builder.build(key)?;
+            e.insert(value);
+        }
+        Ok(entries[&key].clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// A builder that counts how many times it's actually invoked, to prove
+    /// a cache hit skips `build` entirely.
+    struct CountingBuilder {
+        calls: AtomicUsize,
+    }
+
+    impl Cached for CountingBuilder {
+        type Key = u32;
+        type Output = u32;
+        type Error = ();
+
+        fn build(&self, key: u32) -> Result<u32, ()> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(key * 2)
+        }
+
+        fn lock_poisoned() -> () {}
+    }
+
+    #[test]
+    fn test_get_builds_once_per_key() {
+        let cache = Cache::new(CountingBuilder {
+            calls: AtomicUsize::new(0),
+        });
+
+        assert_eq!(cache.get(21).unwrap(), 42);
+        assert_eq!(cache.get(21).unwrap(), 42);
+        assert_eq!(cache.builder.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_get_builds_independently_per_key() {
+        let cache = Cache::new(CountingBuilder {
+            calls: AtomicUsize::new(0),
+        });
+
+        assert_eq!(cache.get(1).unwrap(), 2);
+        assert_eq!(cache.get(2).unwrap(), 4);
+        assert_eq!(cache.builder.calls.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,175 @@
+//! Language detection: map a file path or buffer to a [`Language`] before parsing
+//!
+//! Mirrors how configurable editors resolve a language for a buffer: match
+//! file extensions first, then fall back to a first-line or content regex so
+//! extension-less files (`Makefile`, shebang scripts) still resolve.
+
+use crate::types::Language;
+use regex::Regex;
+use std::path::Path;
+
+/// Describes how to recognize files belonging to one language
+pub struct LanguageConfig {
+    /// Human-readable scope, e.g. `"source.rust"`
+    pub scope: String,
+    /// File extensions / filename suffixes that identify this language, e.g. `["rs"]`
+    pub file_types: Vec<String>,
+    /// Matched against the first line of a file when extension matching fails
+    pub first_line_regex: Option<Regex>,
+    /// Matched against the same first line as `first_line_regex`, e.g. a marker like `.PHONY:`;
+    /// the rest of the file is not searched
+    pub content_regex: Option<Regex>,
+    /// The language this config resolves to
+    pub language: Language,
+}
+
+impl LanguageConfig {
+    /// Creates a config that matches purely on file extension
+    pub fn new(scope: impl Into<String>, file_types: Vec<String>, language: Language) -> Self {
+        Self {
+            scope: scope.into(),
+            file_types,
+            first_line_regex: None,
+            content_regex: None,
+            language,
+        }
+    }
+
+    /// Attaches a first-line regex, for extension-less files like shebang scripts
+    pub fn with_first_line_regex(mut self, pattern: &str) -> Result<Self, regex::Error> {
+        self.first_line_regex = Some(Regex::new(pattern)?);
+        Ok(self)
+    }
+
+    /// Attaches a content regex, for extension-less files like `Makefile`
+    pub fn with_content_regex(mut self, pattern: &str) -> Result<Self, regex::Error> {
+        self.content_regex = Some(Regex::new(pattern)?);
+        Ok(self)
+    }
+
+    fn matches_extension(&self, path: &Path) -> bool {
+        let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else {
+            return false;
+        };
+        self.file_types
+            .iter()
+            .any(|file_type| file_name.ends_with(file_type.as_str()))
+    }
+
+    fn matches_content(&self, first_line: &str) -> bool {
+        self.first_line_regex
+            .as_ref()
+            .is_some_and(|re| re.is_match(first_line))
+            || self
+                .content_regex
+                .as_ref()
+                .is_some_and(|re| re.is_match(first_line))
+    }
+}
+
+/// A registry of [`LanguageConfig`]s used to detect a file's [`Language`]
+///
+/// Built up with [`LanguageRegistry::register`] and queried with
+/// [`LanguageRegistry::detect`].
+#[derive(Default)]
+pub struct LanguageRegistry {
+    configs: Vec<LanguageConfig>,
+}
+
+impl LanguageRegistry {
+    /// Creates an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a language config; earlier registrations are preferred on ties
+    pub fn register(&mut self, config: LanguageConfig) -> &mut Self {
+        self.configs.push(config);
+        self
+    }
+
+    /// Detects the language for `path`, falling back to `first_line` when extension matching fails
+    ///
+    /// Runs in two passes so an extension match never loses to a
+    /// first-line/content match registered earlier: every config is checked
+    /// by extension first, and only if none matches is `first_line`
+    /// consulted against each config's content regexes.
+    pub fn detect(&self, path: &Path, first_line: Option<&str>) -> Option<Language> {
+        if let Some(config) = self.configs.iter().find(|c| c.matches_extension(path)) {
+            return Some(config.language);
+        }
+
+        let first_line = first_line?;
+        self.configs
+            .iter()
+            .find(|config| config.matches_content(first_line))
+            .map(|config| config.language)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry() -> LanguageRegistry {
+        let mut registry = LanguageRegistry::new();
+        registry.register(LanguageConfig::new(
+            "source.rust",
+            vec![".rs".to_string()],
+            Language::Rust,
+        ));
+        registry.register(
+            LanguageConfig::new("source.python", vec![], Language::Python)
+                .with_first_line_regex(r"^#!.*\bpython3?\b")
+                .unwrap(),
+        );
+        registry.register(
+            LanguageConfig::new(
+                "source.makefile",
+                vec!["Makefile".to_string()],
+                Language::Go,
+            )
+            .with_content_regex(r"^\.PHONY:")
+            .unwrap(),
+        );
+        registry
+    }
+
+    #[test]
+    fn test_detect_by_extension() {
+        let registry = registry();
+        assert_eq!(
+            registry.detect(Path::new("src/main.rs"), None),
+            Some(Language::Rust)
+        );
+    }
+
+    #[test]
+    fn test_detect_by_first_line_when_extension_unknown() {
+        let registry = registry();
+        assert_eq!(
+            registry.detect(Path::new("run"), Some("#!/usr/bin/env python3")),
+            Some(Language::Python)
+        );
+    }
+
+    #[test]
+    fn test_detect_returns_none_without_match() {
+        let registry = registry();
+        assert_eq!(
+            registry.detect(Path::new("README.md"), Some("# Hello")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_extension_match_takes_priority_over_content_match() {
+        let registry = registry();
+        // ".rs" extension wins even if the first line would otherwise match
+        // a later-registered content regex.
+        assert_eq!(
+            registry.detect(Path::new("script.rs"), Some("#!/usr/bin/env python3")),
+            Some(Language::Rust)
+        );
+    }
+}
@@ -5,11 +5,31 @@
 //! This module provides a thread-safe cache for tree-sitter parsers,
 //! loading them on-demand as needed for each supported language.
 
+use super::cache::{Cache, Cached};
+use super::language_registry::LanguageRegistry;
 use crate::types::Language;
-use std::collections::HashMap;
-use std::sync::RwLock;
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
 use thiserror::Error;
 
+/// Default number of previously-parsed trees a [`ParserCache`] keeps for incremental reparsing
+pub(crate) const DEFAULT_TREE_CACHE_CAPACITY: usize = 64;
+
+/// Which pre-defined tree-sitter query a language ships for a given purpose
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QueryKind {
+    /// Syntax highlighting captures
+    Highlights,
+    /// Local variable scoping / definition-reference captures
+    Locals,
+    /// Symbol/tag extraction captures (functions, types, etc.)
+    Tags,
+    /// Language injection captures (e.g. embedded HTML/CSS/JS)
+    Injections,
+}
+
 /// Errors that can occur when loading parsers
 #[derive(Debug, Error)]
 pub enum ParserError {
@@ -24,32 +44,221 @@ pub enum ParserError {
     /// Lock poisoned (internal error)
     #[error("Internal cache lock error")]
     LockPoisoned,
+
+    /// Loading a grammar from a shared library failed (dlopen, missing
+    /// symbol, or ABI mismatch)
+    #[error("Failed to load dynamic grammar '{0}'")]
+    GrammarLoadFailed(String),
+
+    /// A `Language::Dynamic` handle didn't correspond to any registered grammar
+    #[error("No dynamic grammar registered for handle {0}")]
+    UnknownDynamicLanguage(u32),
+
+    /// A [`LanguageRegistry`] couldn't determine a language for a path
+    #[error("Could not detect a language for '{0}'")]
+    LanguageNotDetected(PathBuf),
+
+    /// A language's embedded query source failed to compile
+    #[error("Failed to compile {1:?} query for {0:?}")]
+    QueryCompilationFailed(Language, QueryKind),
+
+    /// `Parser::parse` returned `None` (a parse timeout or cancellation flag was set)
+    #[error("Failed to parse source for {0:?}")]
+    ParseFailed(Language),
+}
+
+/// A bounded, insertion-order-evicted cache of the last [`tree_sitter::Tree`] parsed per file path
+///
+/// Backs [`ParserCache::parse_incremental`]: keeping the previous tree around
+/// is what lets a later edit reuse tree-sitter's incremental reparsing
+/// instead of re-walking the whole file. Eviction is FIFO by first insertion
+/// rather than true LRU, mirroring [`Cache`]'s own preference for the
+/// simplest structure that satisfies the actual access pattern (a watcher
+/// revisiting the same handful of files over and over).
+struct TreeCache {
+    capacity: usize,
+    order: VecDeque<PathBuf>,
+    trees: HashMap<PathBuf, tree_sitter::Tree>,
+}
+
+impl TreeCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            order: VecDeque::new(),
+            trees: HashMap::new(),
+        }
+    }
+
+    fn get(&self, path: &Path) -> Option<tree_sitter::Tree> {
+        self.trees.get(path).cloned()
+    }
+
+    fn insert(&mut self, path: PathBuf, tree: tree_sitter::Tree) {
+        if !self.trees.contains_key(&path) {
+            self.order.push_back(path.clone());
+            if self.order.len() > self.capacity {
+                if let Some(evicted) = self.order.pop_front() {
+                    self.trees.remove(&evicted);
+                }
+            }
+        }
+        self.trees.insert(path, tree);
+    }
 }
 
 /// Cache for tree-sitter parsers
 ///
-/// This struct provides lazy loading of parsers for each supported language.
-/// Parsers are loaded on first use and cached for subsequent requests.
-/// The cache is thread-safe and can be shared across threads.
+/// This struct provides lazy loading of parsers for each supported language,
+/// and pools the (expensive, grammar-initialized) parser objects so they can
+/// be reused across calls instead of rebuilt from scratch. The cache is
+/// thread-safe and can be shared across threads.
 ///
 /// # Interior Mutability
 ///
-/// This type uses interior mutability (RwLock) to enable lazy loading of parsers.
-/// This is necessary because parsers are expensive to create and should only be
-/// loaded when needed, but the cache must be usable from an immutable reference.
+/// Parser pools and compiled queries are each stored in a [`Cache`], which
+/// handles the lazy, thread-safe, double-checked-locked construction; a
+/// parser pool's `Output` is itself an `Arc<Mutex<Vec<_>>>` so individual
+/// parsers can still be checked in and out of it.
 pub struct ParserCache {
-    parsers: RwLock<HashMap<Language, tree_sitter::Parser>>,
+    pools: Cache<ParserPoolBuilder>,
+    dynamic_languages: RwLock<HashMap<u32, tree_sitter::Language>>,
+    next_dynamic_id: AtomicU32,
+    queries: Cache<QueryBuilder>,
+    trees: Mutex<TreeCache>,
 }
 
 impl ParserCache {
     /// Creates a new empty parser cache
     pub fn new() -> Self {
         Self {
-            parsers: RwLock::new(HashMap::new()),
+            pools: Cache::new(ParserPoolBuilder),
+            dynamic_languages: RwLock::new(HashMap::new()),
+            next_dynamic_id: AtomicU32::new(0),
+            queries: Cache::new(QueryBuilder),
+            trees: Mutex::new(TreeCache::new(DEFAULT_TREE_CACHE_CAPACITY)),
         }
     }
 
-    /// Gets a parser for the specified language, loading it if necessary
+    /// Gets a compiled query for the specified language, compiling it if necessary
+    ///
+    /// Compiling a `.scm` query source is costly, so results are cached
+    /// behind the same double-checked-locking [`Cache`] that backs parser
+    /// pools. The returned `Arc` can be shared across threads without
+    /// recompiling.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the language is unsupported/not enabled, or if its
+    /// embedded query source for `kind` fails to compile.
+    pub fn get_query(
+        &self,
+        language: Language,
+        kind: QueryKind,
+    ) -> Result<Arc<tree_sitter::Query>, ParserError> {
+        self.queries.get((language, kind))
+    }
+
+    fn compile_query(
+        language: Language,
+        kind: QueryKind,
+    ) -> Result<tree_sitter::Query, ParserError> {
+        let source = Self::query_source(language, kind)?;
+        let ts_language = Self::ts_language(language)?;
+
+        tree_sitter::Query::new(&ts_language, source)
+            .map_err(|_| ParserError::QueryCompilationFailed(language, kind))
+    }
+
+    /// Looks up the compiled-in `tree_sitter::Language` backing a [`Language`]
+    ///
+    /// Shared by [`ParserCache::compile_query`] (for the embedded
+    /// highlights/tags/etc. queries) and by rule-authored queries such as
+    /// [`super::ast_rule::AstRule`]'s, which need the same grammar handle to
+    /// compile a query of their own.
+    pub(crate) fn ts_language(language: Language) -> Result<tree_sitter::Language, ParserError> {
+        match language {
+            #[cfg(feature = "lang-rust")]
+            Language::Rust => Ok(tree_sitter_rust::language()),
+            #[cfg(feature = "lang-typescript")]
+            Language::TypeScript => Ok(tree_sitter_typescript::language_typescript()),
+            #[cfg(feature = "lang-javascript")]
+            Language::JavaScript => Ok(tree_sitter_javascript::language()),
+            #[cfg(feature = "lang-python")]
+            Language::Python => Ok(tree_sitter_python::language()),
+            #[cfg(feature = "lang-go")]
+            Language::Go => Ok(tree_sitter_go::language()),
+            _ => Err(ParserError::UnsupportedLanguage(language)),
+        }
+    }
+
+    /// Looks up the embedded `.scm` query source for a compiled-in language
+    fn query_source(language: Language, kind: QueryKind) -> Result<&'static str, ParserError> {
+        match (language, kind) {
+            #[cfg(feature = "lang-rust")]
+            (Language::Rust, QueryKind::Highlights) => Ok(tree_sitter_rust::HIGHLIGHTS_QUERY),
+            #[cfg(feature = "lang-rust")]
+            (Language::Rust, QueryKind::Tags) => Ok(tree_sitter_rust::TAGS_QUERY),
+            #[cfg(feature = "lang-typescript")]
+            (Language::TypeScript, QueryKind::Highlights) => {
+                Ok(tree_sitter_typescript::HIGHLIGHTS_QUERY)
+            }
+            #[cfg(feature = "lang-javascript")]
+            (Language::JavaScript, QueryKind::Highlights) => {
+                Ok(tree_sitter_javascript::HIGHLIGHT_QUERY)
+            }
+            #[cfg(feature = "lang-python")]
+            (Language::Python, QueryKind::Highlights) => Ok(tree_sitter_python::HIGHLIGHTS_QUERY),
+            #[cfg(feature = "lang-go")]
+            (Language::Go, QueryKind::Highlights) => Ok(tree_sitter_go::HIGHLIGHTS_QUERY),
+            _ => Err(ParserError::UnsupportedLanguage(language)),
+        }
+    }
+
+    /// Registers a tree-sitter grammar loaded at runtime from a shared library
+    ///
+    /// Loads `lib_path` through a [`tree_sitter_loader::Loader`] — the same
+    /// mechanism `tree-sitter-cli` uses to load out-of-tree grammars — and
+    /// resolves `symbol` (conventionally `tree_sitter_<name>`) as the
+    /// grammar's language constructor. All of the `dlopen`/symbol-resolution
+    /// unsafety this requires lives inside `tree-sitter-loader` rather than
+    /// here, so this crate's `#![forbid(unsafe_code)]` still holds.
+    ///
+    /// On success, returns a `Language::Dynamic` handle that can be passed to
+    /// [`ParserCache::checkout`] and [`ParserCache::get_parser`] exactly like
+    /// a compiled-in language.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParserError::GrammarLoadFailed`] if the library can't be
+    /// opened or `symbol` can't be resolved to a valid `TSLanguage`.
+    pub fn register_dynamic_language(
+        &self,
+        name: &str,
+        lib_path: &Path,
+        symbol: &str,
+    ) -> Result<Language, ParserError> {
+        let loader = tree_sitter_loader::Loader::new()
+            .map_err(|_| ParserError::GrammarLoadFailed(name.to_string()))?;
+        let language = loader
+            .load_language_at_path_with_symbol(lib_path, symbol)
+            .map_err(|_| ParserError::GrammarLoadFailed(name.to_string()))?;
+
+        let id = self.next_dynamic_id.fetch_add(1, Ordering::SeqCst);
+        let mut dynamic_languages = self
+            .dynamic_languages
+            .write()
+            .map_err(|_| ParserError::LockPoisoned)?;
+        dynamic_languages.insert(id, language);
+
+        Ok(Language::Dynamic(id))
+    }
+
+    /// Checks out a parser for the specified language, reusing a pooled one if idle
+    ///
+    /// Returns an RAII [`PooledParser`] guard. On drop, the parser is returned
+    /// to the pool so the next `checkout` for the same language can reset and
+    /// reuse it rather than paying grammar-initialization cost again.
     ///
     /// # Errors
     ///
@@ -57,50 +266,141 @@ impl ParserCache {
     /// - The language is not supported
     /// - The language feature is not enabled
     /// - Parser initialization fails
-    ///
-    /// # Note on Return Value
-    ///
-    /// This method returns a newly created Parser rather than a reference because
-    /// tree_sitter::Parser instances are lightweight and cheap to clone. The actual
-    /// grammar data is shared internally, so this approach avoids lifetime complications
-    /// while maintaining efficient memory usage.
-    pub fn get_parser(&self, language: Language) -> Result<tree_sitter::Parser, ParserError> {
-        // First try to read from cache
+    pub fn checkout(&self, language: Language) -> Result<PooledParser, ParserError> {
+        let pool = self.pools.get(language)?;
+
         {
-            let parsers = self.parsers.read().map_err(|_| ParserError::LockPoisoned)?;
-            if parsers.contains_key(&language) {
-                // Parser exists, create a new instance with same language
-                return Self::create_parser_for_language(language);
+            let mut idle = pool.lock().map_err(|_| ParserError::LockPoisoned)?;
+            if let Some(mut parser) = idle.pop() {
+                parser.reset();
+                return Ok(PooledParser {
+                    pool,
+                    parser: Some(parser),
+                });
             }
+            // Pool exists but every parser in it is currently checked out;
+            // fall through and build a fresh one below.
         }
 
-        // Cache miss - acquire write lock and create parser
-        let mut parsers = self
-            .parsers
-            .write()
-            .map_err(|_| ParserError::LockPoisoned)?;
+        let parser = self.create_parser_for_language(language)?;
+        Ok(PooledParser {
+            pool,
+            parser: Some(parser),
+        })
+    }
 
-        // Double-check in case another thread added it while we were waiting
-        if let std::collections::hash_map::Entry::Vacant(e) = parsers.entry(language) {
-            let parser = Self::create_parser_for_language(language)?;
-            e.insert(parser);
-        }
+    /// Gets a parser for the specified language, loading it if necessary
+    ///
+    /// Thin alias for [`ParserCache::checkout`]: the returned [`PooledParser`]
+    /// derefs to `tree_sitter::Parser` for callers that just want to call
+    /// `.parse(...)`, and still returns itself to the pool on drop so the
+    /// cost of grammar initialization is actually amortized across calls.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The language is not supported
+    /// - The language feature is not enabled
+    /// - Parser initialization fails
+    pub fn get_parser(&self, language: Language) -> Result<PooledParser, ParserError> {
+        self.checkout(language)
+    }
 
-        // Return a new parser instance
-        Self::create_parser_for_language(language)
+    /// Detects `path`'s language via `registry` and checks out a parser for it
+    ///
+    /// Lets a caller go straight from a file path (and, for extension-less
+    /// files, its first line) to a ready parser without first looking up the
+    /// [`Language`] itself.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParserError::LanguageNotDetected`] if `registry` can't
+    /// resolve a language for `path`, or any error [`ParserCache::checkout`]
+    /// can return for the detected language.
+    pub fn checkout_for_path(
+        &self,
+        path: &Path,
+        first_line: Option<&str>,
+        registry: &LanguageRegistry,
+    ) -> Result<PooledParser, ParserError> {
+        let language = registry
+            .detect(path, first_line)
+            .ok_or_else(|| ParserError::LanguageNotDetected(path.to_path_buf()))?;
+        self.checkout(language)
+    }
+
+    /// Reparses `new_content` for `path`, reusing the previously cached tree for that path if present
+    ///
+    /// Applies `edit` (tree-sitter's byte/point delta describing what
+    /// changed) to the tree cached from the last call for `path`, then hands
+    /// it to the parser as the second argument to `parse` so tree-sitter only
+    /// re-walks the subtrees the edit actually touched, instead of the whole
+    /// file. Falls back to a full parse (passing `None`) when `path` has no
+    /// cached tree yet — e.g. its first scan. The resulting tree replaces the
+    /// cached one, evicting the oldest entry once the cache is at capacity.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `language` is unsupported/not enabled, parser
+    /// initialization fails, or the parse itself fails (a timeout or
+    /// cancellation flag was set).
+    pub fn parse_incremental(
+        &self,
+        path: &Path,
+        language: Language,
+        new_content: &str,
+        edit: tree_sitter::InputEdit,
+    ) -> Result<tree_sitter::Tree, ParserError> {
+        let mut parser = self.checkout(language)?;
+
+        let previous_tree = {
+            let mut cache = self.trees.lock().map_err(|_| ParserError::LockPoisoned)?;
+            cache.get(path).map(|mut tree| {
+                tree.edit(&edit);
+                tree
+            })
+        };
+
+        let tree = parser
+            .parse(new_content, previous_tree.as_ref())
+            .ok_or(ParserError::ParseFailed(language))?;
+
+        let mut cache = self.trees.lock().map_err(|_| ParserError::LockPoisoned)?;
+        cache.insert(path.to_path_buf(), tree.clone());
+        Ok(tree)
     }
 
     /// Creates a parser for the given language
-    fn create_parser_for_language(language: Language) -> Result<tree_sitter::Parser, ParserError> {
+    fn create_parser_for_language(
+        &self,
+        language: Language,
+    ) -> Result<tree_sitter::Parser, ParserError> {
         match language {
             Language::Rust => Self::create_rust_parser(),
             Language::TypeScript => Self::create_typescript_parser(),
             Language::JavaScript => Self::create_javascript_parser(),
             Language::Python => Self::create_python_parser(),
             Language::Go => Self::create_go_parser(),
+            Language::Dynamic(id) => self.create_dynamic_parser(id),
         }
     }
 
+    fn create_dynamic_parser(&self, id: u32) -> Result<tree_sitter::Parser, ParserError> {
+        let dynamic_languages = self
+            .dynamic_languages
+            .read()
+            .map_err(|_| ParserError::LockPoisoned)?;
+        let language = dynamic_languages
+            .get(&id)
+            .ok_or(ParserError::UnknownDynamicLanguage(id))?;
+
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(language)
+            .map_err(|_| ParserError::InitializationFailed(Language::Dynamic(id)))?;
+        Ok(parser)
+    }
+
     fn create_rust_parser() -> Result<tree_sitter::Parser, ParserError> {
         #[cfg(feature = "lang-rust")]
         {
@@ -183,6 +483,83 @@ impl Default for ParserCache {
     }
 }
 
+/// Builds an empty parser pool for a language, for use with [`Cache`]
+///
+/// The cached unit here is the pool itself (an `Arc<Mutex<Vec<Parser>>>`),
+/// not a single parser — [`ParserCache`] needs many live instances per
+/// language, which a plain "one value per key" cache can't model. Getting a
+/// pool from the cache is itself the lazy "register a pool for this language
+/// the first time it's requested" step; checking individual parsers in and
+/// out of it is [`ParserCache::checkout`]'s job.
+pub struct ParserPoolBuilder;
+
+impl Cached for ParserPoolBuilder {
+    type Key = Language;
+    type Output = Arc<Mutex<Vec<tree_sitter::Parser>>>;
+    type Error = ParserError;
+
+    fn build(&self, _language: Language) -> Result<Self::Output, ParserError> {
+        Ok(Arc::new(Mutex::new(Vec::new())))
+    }
+
+    fn lock_poisoned() -> ParserError {
+        ParserError::LockPoisoned
+    }
+}
+
+/// Compiles a language's embedded query source, for use with [`Cache`]
+pub struct QueryBuilder;
+
+impl Cached for QueryBuilder {
+    type Key = (Language, QueryKind);
+    type Output = Arc<tree_sitter::Query>;
+    type Error = ParserError;
+
+    fn build(&self, (language, kind): (Language, QueryKind)) -> Result<Self::Output, ParserError> {
+        ParserCache::compile_query(language, kind).map(Arc::new)
+    }
+
+    fn lock_poisoned() -> ParserError {
+        ParserError::LockPoisoned
+    }
+}
+
+/// An RAII handle to a parser checked out of a [`ParserCache`]
+///
+/// Dereferences to the underlying [`tree_sitter::Parser`]. When dropped, the
+/// parser is returned to the pool it was checked out from, so a later
+/// `checkout` for the same language can reuse it instead of reinitializing
+/// the grammar from scratch.
+pub struct PooledParser {
+    pool: Arc<Mutex<Vec<tree_sitter::Parser>>>,
+    parser: Option<tree_sitter::Parser>,
+}
+
+impl std::ops::Deref for PooledParser {
+    type Target = tree_sitter::Parser;
+
+    fn deref(&self) -> &Self::Target {
+        self.parser.as_ref().expect("parser is only taken on drop")
+    }
+}
+
+impl std::ops::DerefMut for PooledParser {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.parser.as_mut().expect("parser is only taken on drop")
+    }
+}
+
+impl Drop for PooledParser {
+    fn drop(&mut self) {
+        let Some(parser) = self.parser.take() else {
+            return;
+        };
+        if let Ok(mut idle) = self.pool.lock() {
+            idle.push(parser);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -270,11 +647,70 @@ mod tests {
             "Second parser load should succeed (from cache)"
         );
 
-        // Verify the cache was populated by checking the internal state
-        let parsers = cache.parsers.read().unwrap();
+        // Verify the same pool is handed back for repeated lookups, rather
+        // than a fresh one being registered each time.
+        let pool_a = cache.pools.get(Language::Rust).unwrap();
+        let pool_b = cache.pools.get(Language::Rust).unwrap();
         assert!(
-            parsers.contains_key(&Language::Rust),
-            "Parser should be cached"
+            Arc::ptr_eq(&pool_a, &pool_b),
+            "Pool should be cached, not rebuilt, for Rust"
+        );
+    }
+
+    #[cfg(feature = "lang-rust")]
+    #[test]
+    fn test_checkout_recycles_parser_on_drop() {
+        let cache = ParserCache::new();
+
+        {
+            let _parser = cache.checkout(Language::Rust).unwrap();
+        } // guard drops here, returning the parser to the pool
+
+        let pool = cache.pools.get(Language::Rust).unwrap();
+        let idle = pool.lock().unwrap();
+        assert_eq!(
+            idle.len(),
+            1,
+            "dropped parser should be returned to its pool"
+        );
+    }
+
+    #[cfg(feature = "lang-rust")]
+    #[test]
+    fn test_get_parser_recycles_through_the_pool_on_drop() {
+        let cache = ParserCache::new();
+
+        {
+            let _parser = cache.get_parser(Language::Rust).unwrap();
+        } // guard drops here, returning the parser to the pool
+
+        let pool = cache.pools.get(Language::Rust).unwrap();
+        let idle = pool.lock().unwrap();
+        assert_eq!(
+            idle.len(),
+            1,
+            "get_parser's pooled guard should be returned to its pool on drop, not detached"
+        );
+    }
+
+    #[cfg(feature = "lang-rust")]
+    #[test]
+    fn test_checkout_reuses_pooled_parser_instead_of_rebuilding() {
+        let cache = ParserCache::new();
+
+        let first = cache.checkout(Language::Rust).unwrap();
+        drop(first);
+
+        // With exactly one parser ever built and returned to the pool, a
+        // second checkout must pop that same instance rather than building
+        // another, leaving the pool empty while it's checked out.
+        let _second = cache.checkout(Language::Rust).unwrap();
+        let pool = cache.pools.get(Language::Rust).unwrap();
+        let idle = pool.lock().unwrap();
+        assert_eq!(
+            idle.len(),
+            0,
+            "the only pooled parser should be checked out"
         );
     }
 
@@ -288,4 +724,173 @@ mod tests {
             Err(ParserError::UnsupportedLanguage(Language::Rust))
         ));
     }
+
+    #[test]
+    fn test_register_dynamic_language_reports_load_failure_for_missing_library() {
+        let cache = ParserCache::new();
+        let result = cache.register_dynamic_language(
+            "made-up-language",
+            std::path::Path::new("/nonexistent/libtree-sitter-made-up.so"),
+            "tree_sitter_made_up_language",
+        );
+        assert!(matches!(result, Err(ParserError::GrammarLoadFailed(_))));
+    }
+
+    #[test]
+    fn test_unknown_dynamic_language_handle_is_reported() {
+        let cache = ParserCache::new();
+        let result = cache.get_parser(Language::Dynamic(42));
+        assert!(matches!(
+            result,
+            Err(ParserError::UnknownDynamicLanguage(42))
+        ));
+    }
+
+    #[cfg(feature = "lang-rust")]
+    #[test]
+    fn test_checkout_for_path_detects_language_from_extension() {
+        use super::super::language_registry::{LanguageConfig, LanguageRegistry};
+
+        let mut registry = LanguageRegistry::new();
+        registry.register(LanguageConfig::new(
+            "source.rust",
+            vec![".rs".to_string()],
+            Language::Rust,
+        ));
+
+        let cache = ParserCache::new();
+        let parser = cache.checkout_for_path(Path::new("src/main.rs"), None, &registry);
+        assert!(parser.is_ok());
+    }
+
+    #[test]
+    fn test_checkout_for_path_reports_undetected_language() {
+        use super::super::language_registry::LanguageRegistry;
+
+        let registry = LanguageRegistry::new();
+        let cache = ParserCache::new();
+        let result = cache.checkout_for_path(Path::new("README"), None, &registry);
+        assert!(matches!(result, Err(ParserError::LanguageNotDetected(_))));
+    }
+
+    #[cfg(feature = "lang-rust")]
+    #[test]
+    fn test_get_query_compiles_and_caches_highlights_query() {
+        let cache = ParserCache::new();
+        let query = cache.get_query(Language::Rust, QueryKind::Highlights);
+        assert!(query.is_ok(), "Rust highlights query should compile");
+
+        let cached = cache
+            .get_query(Language::Rust, QueryKind::Highlights)
+            .unwrap();
+        assert!(
+            Arc::ptr_eq(&query.unwrap(), &cached),
+            "second get_query call should return the cached Arc"
+        );
+    }
+
+    #[cfg(feature = "lang-rust")]
+    #[test]
+    fn test_get_query_rejects_unsupported_kind_for_language() {
+        let cache = ParserCache::new();
+        // Rust doesn't embed an injections query in this crate.
+        let result = cache.get_query(Language::Rust, QueryKind::Injections);
+        assert!(result.is_err());
+    }
+
+    #[cfg(feature = "lang-rust")]
+    fn insertion_edit(
+        start_byte: usize,
+        old_end_byte: usize,
+        new_end_byte: usize,
+    ) -> tree_sitter::InputEdit {
+        tree_sitter::InputEdit {
+            start_byte,
+            old_end_byte,
+            new_end_byte,
+            start_position: tree_sitter::Point::new(0, start_byte),
+            old_end_position: tree_sitter::Point::new(0, old_end_byte),
+            new_end_position: tree_sitter::Point::new(0, new_end_byte),
+        }
+    }
+
+    #[cfg(feature = "lang-rust")]
+    #[test]
+    fn test_parse_incremental_full_parses_first_call() {
+        let cache = ParserCache::new();
+        let path = Path::new("src/main.rs");
+
+        let tree = cache
+            .parse_incremental(path, Language::Rust, "fn a() {}", insertion_edit(4, 4, 4))
+            .unwrap();
+
+        assert_eq!(tree.root_node().kind(), "source_file");
+        assert!(!tree.root_node().has_error());
+    }
+
+    #[cfg(feature = "lang-rust")]
+    #[test]
+    fn test_parse_incremental_reuses_cached_tree_on_second_call() {
+        let cache = ParserCache::new();
+        let path = Path::new("src/main.rs");
+
+        cache
+            .parse_incremental(path, Language::Rust, "fn a() {}", insertion_edit(4, 4, 4))
+            .unwrap();
+
+        // Insert "b" right after "a" (byte offset 4, before the parens).
+        let tree = cache
+            .parse_incremental(path, Language::Rust, "fn ab() {}", insertion_edit(4, 4, 5))
+            .unwrap();
+
+        assert_eq!(tree.root_node().kind(), "source_file");
+        assert!(!tree.root_node().has_error());
+        assert_eq!(cache.trees.lock().unwrap().trees.len(), 1);
+    }
+
+    #[cfg(feature = "lang-rust")]
+    #[test]
+    fn test_parse_incremental_tracks_a_separate_tree_per_path() {
+        let cache = ParserCache::new();
+
+        cache
+            .parse_incremental(
+                Path::new("src/a.rs"),
+                Language::Rust,
+                "fn a() {}",
+                insertion_edit(0, 0, 0),
+            )
+            .unwrap();
+        cache
+            .parse_incremental(
+                Path::new("src/b.rs"),
+                Language::Rust,
+                "fn b() {}",
+                insertion_edit(0, 0, 0),
+            )
+            .unwrap();
+
+        assert_eq!(cache.trees.lock().unwrap().trees.len(), 2);
+    }
+
+    #[cfg(feature = "lang-rust")]
+    #[test]
+    fn test_tree_cache_evicts_oldest_entry_past_capacity() {
+        let mut cache = TreeCache::new(2);
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&tree_sitter_rust::language()).unwrap();
+
+        let tree_a = parser.parse("fn a() {}", None).unwrap();
+        let tree_b = parser.parse("fn b() {}", None).unwrap();
+        let tree_c = parser.parse("fn c() {}", None).unwrap();
+
+        cache.insert(PathBuf::from("a.rs"), tree_a);
+        cache.insert(PathBuf::from("b.rs"), tree_b);
+        cache.insert(PathBuf::from("c.rs"), tree_c);
+
+        assert_eq!(cache.trees.len(), 2);
+        assert!(cache.get(Path::new("a.rs")).is_none());
+        assert!(cache.get(Path::new("b.rs")).is_some());
+        assert!(cache.get(Path::new("c.rs")).is_some());
+    }
 }